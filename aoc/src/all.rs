@@ -0,0 +1,327 @@
+use std::{
+    num::NonZeroUsize,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail};
+use aoc::days;
+use aoc_core::{
+    alloc::{self, AllocStats},
+    cli::{self, Format, Input, Options, PartReport, Report},
+    Answer, Puzzle,
+};
+
+use crate::fuzz::{panic_message, silence_panics};
+
+/// Runs `job` on every item, on a pool of as many threads as there are cores, giving results back
+/// in the order of the items.
+fn run_in_pool<T: Sync, R: Send>(items: &[T], job: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(items.len());
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|s| {
+        for _ in 0..threads {
+            let (next, job, sender) = (&next, &job, sender.clone());
+
+            s.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else {
+                    break;
+                };
+                // the receiver outlives every worker
+                let _ = sender.send((i, job(item)));
+            });
+        }
+    });
+    drop(sender);
+
+    let mut results = receiver.into_iter().collect::<Vec<_>>();
+    results.sort_by_key(|&(i, _)| i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
+
+#[derive(Debug)]
+enum Outcome {
+    Solved(Report),
+    Failed(anyhow::Error),
+    Panicked(String),
+}
+
+/// How running a single day went, and how long it took from start to finish.
+#[derive(Debug)]
+struct DayRun {
+    day: u8,
+    outcome: Outcome,
+    wall: Duration,
+}
+
+impl DayRun {
+    fn failed(&self) -> bool {
+        match &self.outcome {
+            Outcome::Solved(report) => report.check().is_err(),
+            Outcome::Failed(_) | Outcome::Panicked(_) => true,
+        }
+    }
+}
+
+/// Runs `solve`, turning whatever goes wrong, panics included, into the outcome.
+fn isolate(day: u8, solve: impl FnOnce() -> Result<Report, anyhow::Error>) -> DayRun {
+    let start = Instant::now();
+    let outcome = match panic::catch_unwind(AssertUnwindSafe(solve)) {
+        Ok(Ok(report)) => Outcome::Solved(report),
+        Ok(Err(e)) => Outcome::Failed(e),
+        Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
+    };
+
+    DayRun {
+        day,
+        outcome,
+        wall: start.elapsed(),
+    }
+}
+
+fn run_day(puzzle: &Puzzle, options: &Options) -> DayRun {
+    let input = options
+        .input
+        .clone()
+        .unwrap_or_else(|| Input::Path(days::input_path(puzzle.day)));
+
+    isolate(puzzle.day, || cli::execute(puzzle, &input, options))
+}
+
+/// Single line of the summary table.
+#[derive(Debug, PartialEq, Eq)]
+struct Row {
+    day: u8,
+    part: String,
+    answer: String,
+    status: &'static str,
+    time: Duration,
+    alloc: AllocStats,
+    error: Option<String>,
+}
+
+fn rows(run: &DayRun) -> Vec<Row> {
+    let failure = |status, error| Row {
+        day: run.day,
+        part: "-".to_string(),
+        answer: "-".to_string(),
+        status,
+        time: run.wall,
+        alloc: AllocStats::default(),
+        error: Some(error),
+    };
+
+    match &run.outcome {
+        Outcome::Solved(report) => report
+            .parts
+            .iter()
+            .map(
+                |PartReport {
+                     part,
+                     answer,
+                     elapsed,
+                     alloc,
+                     ..
+                 }| {
+                    let (answer, status, error) = match answer {
+                        Ok(Answer::Unimplemented) => ("-".to_string(), "unimplemented", None),
+                        Ok(answer) => (answer.to_string(), "ok", None),
+                        Err(e) => ("-".to_string(), "error", Some(format!("{e:#}"))),
+                    };
+
+                    Row {
+                        day: run.day,
+                        part: part.to_string(),
+                        answer,
+                        status,
+                        time: *elapsed,
+                        alloc: *alloc,
+                        error,
+                    }
+                },
+            )
+            .collect(),
+        Outcome::Failed(e) => vec![failure("error", format!("{e:#}"))],
+        Outcome::Panicked(message) => vec![failure("panic", format!("panicked: {message}"))],
+    }
+}
+
+fn print_table(runs: &[DayRun]) {
+    let rows = runs.iter().flat_map(rows).collect::<Vec<_>>();
+    let width = rows
+        .iter()
+        .map(|r| r.answer.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+
+    println!(
+        "{:<4} {:<5} {:<width$} {:<14} {:>12}{}",
+        "Day",
+        "Part",
+        "Answer",
+        "Status",
+        "Time",
+        if alloc::ENABLED { "  Allocations" } else { "" }
+    );
+
+    for row in &rows {
+        println!(
+            "{:<4} {:<5} {:<width$} {:<14} {:>12}{}",
+            format!("{:02}", row.day),
+            row.part,
+            row.answer,
+            row.status,
+            format!("{:?}", row.time),
+            if alloc::ENABLED {
+                format!("  {}", row.alloc)
+            } else {
+                String::new()
+            }
+        );
+    }
+
+    let errors = rows
+        .iter()
+        .filter_map(|r| Some((r, r.error.as_ref()?)))
+        .collect::<Vec<_>>();
+
+    if !errors.is_empty() {
+        println!();
+    }
+
+    for (row, error) in errors {
+        match row.part.as_str() {
+            "-" => println!("Day {:02}: {}", row.day, error),
+            part => println!("Day {:02} part {}: {}", row.day, part, error),
+        }
+    }
+}
+
+/// Solves every day at once, each on its own so a panic or error in one doesn't stop the rest,
+/// then reports them all in day order.
+///
+/// # Errors
+///
+/// Returns error when any of the days failed.
+pub fn run_all(options: &Options) -> Result<(), anyhow::Error> {
+    let start = Instant::now();
+
+    // panics are reported in the summary, rather than printed mid-run
+    let runs = silence_panics(|| run_in_pool(days::DAYS, |puzzle| run_day(puzzle, options)));
+
+    match options.format {
+        Format::Text => {
+            print_table(&runs);
+            println!("\n{} day(s) in {:?}", runs.len(), start.elapsed());
+        }
+        Format::Json => {
+            for run in &runs {
+                match &run.outcome {
+                    Outcome::Solved(report) => println!("{}", report.to_json()),
+                    Outcome::Failed(e) => {
+                        println!("{}", cli::failure_to_json(run.day, options, e));
+                    }
+                    Outcome::Panicked(message) => {
+                        let e = anyhow!("panicked: {}", message);
+                        println!("{}", cli::failure_to_json(run.day, options, &e));
+                    }
+                }
+            }
+        }
+    }
+
+    let failures = runs.iter().filter(|r| r.failed()).count();
+
+    if failures > 0 {
+        bail!("{} day(s) failed", failures);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Part;
+
+    #[test]
+    fn test_pool_keeps_order() {
+        let items = (0..100).collect::<Vec<u64>>();
+        assert_eq!(
+            run_in_pool(&items, |&n| n * n),
+            items.iter().map(|n| n * n).collect::<Vec<_>>()
+        );
+        assert_eq!(run_in_pool(&[] as &[u8], |&n| n), vec![]);
+    }
+
+    #[test]
+    fn test_failing_days_dont_stop_the_rest() {
+        let runs = run_in_pool(&[1, 2, 3], |&day| {
+            isolate(day, || match day {
+                1 => panic!("boom"),
+                2 => Err(anyhow!("bad input")),
+                _ => Ok(Report {
+                    day,
+                    repeat: 1,
+                    parse: Duration::ZERO,
+                    parse_alloc: AllocStats::default(),
+                    parts: vec![PartReport {
+                        part: Part::One,
+                        answer: Ok(Answer::Integer(42)),
+                        elapsed: Duration::from_millis(1),
+                        alloc: AllocStats::default(),
+                        trace: vec![],
+                    }],
+                }),
+            })
+        });
+
+        assert!(matches!(&runs[0].outcome, Outcome::Panicked(m) if m == "boom"));
+        assert!(matches!(&runs[1].outcome, Outcome::Failed(_)));
+        assert_eq!(
+            runs.iter().map(DayRun::failed).collect::<Vec<_>>(),
+            [true, true, false]
+        );
+
+        assert_eq!(rows(&runs[0])[0].status, "panic");
+        assert_eq!(rows(&runs[1])[0].error.as_deref(), Some("bad input"));
+        assert_eq!(
+            rows(&runs[2]),
+            vec![Row {
+                day: 3,
+                part: "1".to_string(),
+                answer: "42".to_string(),
+                status: "ok",
+                time: Duration::from_millis(1),
+                alloc: AllocStats::default(),
+                error: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_every_day_solves_its_examples() {
+        let options = Options {
+            input: Some(Input::Example),
+            ..Options::default()
+        };
+        let runs = run_in_pool(days::DAYS, |puzzle| run_day(puzzle, &options));
+
+        assert_eq!(runs.len(), days::DAYS.len());
+        for (run, puzzle) in runs.iter().zip(days::DAYS) {
+            assert_eq!(run.day, puzzle.day);
+            assert!(!run.failed(), "{:?}", run);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::{collections::BTreeMap, fmt, fs, io, str::FromStr};
+
+use anyhow::{bail, Context};
+use aoc_core::Part;
+
+/// Default location of the answers file, relative to the directory the runner is started from.
+/// It's git-ignored, as answers depend on personal puzzle inputs.
+pub const DEFAULT_PATH: &str = "answers.txt";
+
+/// Known-good answers to the real puzzle inputs, stored one per line as `<day> <part> <answer>`.
+/// Blank lines and lines starting with `#` are ignored.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u8, Part), String>);
+
+impl Answers {
+    /// Loads answers from `path`, treating a missing file as empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns error when the file exists but can't be read or parsed.
+    pub fn load(path: &str) -> Result<Self, anyhow::Error> {
+        match fs::read_to_string(path) {
+            Ok(content) => content
+                .parse()
+                .with_context(|| format!("couldn't parse {path}")),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("couldn't read {path}")),
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Returns error when the file can't be written.
+    pub fn save(&self, path: &str) -> Result<(), anyhow::Error> {
+        fs::write(path, self.to_string()).with_context(|| format!("couldn't write {path}"))
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, answer: String) {
+        self.0.insert((day, part), answer);
+    }
+}
+
+impl FromStr for Answers {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Self::default();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(3, ' ');
+            let (Some(day), Some(part), Some(answer)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                bail!(
+                    "line {}: expected `<day> <part> <answer>`, got {:?}",
+                    i + 1,
+                    line
+                );
+            };
+
+            let day = day
+                .parse()
+                .with_context(|| format!("line {}: couldn't parse day from {:?}", i + 1, day))?;
+            let part = part.parse().with_context(|| format!("line {}", i + 1))?;
+
+            answers.insert(day, part, answer.to_string());
+        }
+
+        Ok(answers)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# <day> <part> <answer>")?;
+
+        for ((day, part), answer) in &self.0 {
+            writeln!(f, "{day:02} {part} {answer}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "\
+# <day> <part> <answer>
+01 1 142
+01 2 281
+
+05 1 35
+";
+
+    #[test]
+    fn test_answers_parsing() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        assert_eq!(answers.get(1, Part::One), Some("142"));
+        assert_eq!(answers.get(1, Part::Two), Some("281"));
+        assert_eq!(answers.get(5, Part::One), Some("35"));
+        assert_eq!(answers.get(5, Part::Two), None);
+    }
+
+    #[test]
+    fn test_answers_with_spaces() {
+        let answers: Answers = "10 2 ABC DEF".parse().unwrap();
+        assert_eq!(answers.get(10, Part::Two), Some("ABC DEF"));
+    }
+
+    #[test]
+    fn test_answers_round_trip() {
+        let mut answers: Answers = ANSWERS.parse().unwrap();
+        answers.insert(3, Part::Two, "467835".to_string());
+
+        let reparsed: Answers = answers.to_string().parse().unwrap();
+        assert_eq!(reparsed, answers);
+        assert!(answers.to_string().contains("03 2 467835\n"));
+    }
+
+    #[test]
+    fn test_answers_errors() {
+        assert!("01 1".parse::<Answers>().is_err());
+        assert!("xx 1 142".parse::<Answers>().is_err());
+        assert!("01 3 142".parse::<Answers>().is_err());
+    }
+}
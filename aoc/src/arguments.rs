@@ -0,0 +1,47 @@
+//! Argument handling shared by the subcommands.
+
+use std::slice::Iter;
+
+use anyhow::{anyhow, Context};
+
+/// Value given to `flag`, which is the next argument.
+pub(crate) fn value<'a>(
+    args: &mut Iter<'a, String>,
+    flag: &str,
+) -> Result<&'a String, anyhow::Error> {
+    args.next()
+        .ok_or_else(|| anyhow!("missing value for {}", flag))
+}
+
+/// Day given as a positional argument. Anything else is unexpected, and reported along with
+/// the subcommand's `usage`.
+pub(crate) fn day(arg: &str, usage: &str) -> Result<u8, anyhow::Error> {
+    arg.parse()
+        .with_context(|| format!("unexpected argument {arg:?}\n\n{usage}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value() {
+        let args = ["--seed", "7"].map(str::to_string);
+        let mut args = args.iter();
+        let flag = args.next().unwrap();
+        assert_eq!(value(&mut args, flag).unwrap(), "7");
+        assert_eq!(
+            value(&mut args, flag).unwrap_err().to_string(),
+            "missing value for --seed"
+        );
+    }
+
+    #[test]
+    fn test_day() {
+        assert_eq!(day("12", "aoc x").unwrap(), 12);
+        assert_eq!(
+            day("--nope", "aoc x").unwrap_err().to_string(),
+            "unexpected argument \"--nope\"\n\naoc x"
+        );
+    }
+}
@@ -0,0 +1,563 @@
+use std::{
+    fmt, fs,
+    hint::black_box,
+    io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Context};
+use aoc::days;
+use aoc_core::{json::Value, Part, Puzzle};
+
+use crate::arguments;
+
+pub const USAGE: &str = "\
+aoc bench [<day>...] [--example] [--warmup <n>] [--samples <n>] [--baseline <path>] [--save]
+          [--force] [--threshold <percent>]";
+
+/// Default location of stored results, relative to the directory the runner is started from.
+/// It's git-ignored, as timings only make sense on the machine that produced them.
+pub const DEFAULT_BASELINE: &str = "bench.json";
+
+#[derive(Debug, PartialEq)]
+struct Settings {
+    days: Vec<u8>,
+    example: bool,
+    warmup: usize,
+    samples: usize,
+    baseline: String,
+    save: bool,
+    force: bool,
+    threshold: f64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            days: vec![],
+            example: false,
+            warmup: 3,
+            samples: 20,
+            baseline: DEFAULT_BASELINE.to_string(),
+            save: false,
+            force: false,
+            threshold: 10.0,
+        }
+    }
+}
+
+impl Settings {
+    fn parse(args: &[String]) -> Result<Self, anyhow::Error> {
+        let mut settings = Self::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--example" => settings.example = true,
+                "--warmup" => settings.warmup = arguments::value(&mut args, arg)?.parse()?,
+                "--samples" => settings.samples = arguments::value(&mut args, arg)?.parse()?,
+                "--baseline" => settings.baseline = arguments::value(&mut args, arg)?.clone(),
+                "--save" => settings.save = true,
+                "--force" => settings.force = true,
+                "--threshold" => settings.threshold = arguments::value(&mut args, arg)?.parse()?,
+                day => settings.days.push(arguments::day(day, USAGE)?),
+            }
+        }
+
+        if settings.samples == 0 {
+            bail!("at least one sample is needed");
+        }
+
+        Ok(settings)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse => write!(f, "parse"),
+            Self::Solve(part) => write!(f, "part_{part}"),
+        }
+    }
+}
+
+impl Phase {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "parse" => Some(Self::Parse),
+            "part_1" => Some(Self::Solve(Part::One)),
+            "part_2" => Some(Self::Solve(Part::Two)),
+            _ => None,
+        }
+    }
+}
+
+/// Which input a measurement was taken on. Examples are much smaller than real inputs, so their
+/// timings are only ever compared with each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum InputKind {
+    Example,
+    Real,
+}
+
+impl fmt::Display for InputKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Example => write!(f, "example"),
+            Self::Real => write!(f, "input"),
+        }
+    }
+}
+
+impl InputKind {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "example" => Some(Self::Example),
+            "input" => Some(Self::Real),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Measurement {
+    day: u8,
+    input: InputKind,
+    phase: Phase,
+    samples: usize,
+    median: Duration,
+    p95: Duration,
+}
+
+/// Nearest-rank percentile of already sorted samples.
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn measure<T>(mut f: impl FnMut() -> T, settings: &Settings) -> Vec<Duration> {
+    for _ in 0..settings.warmup {
+        black_box(f());
+    }
+
+    let mut samples = (0..settings.samples)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect::<Vec<_>>();
+
+    samples.sort_unstable();
+    samples
+}
+
+impl Measurement {
+    /// Whether both measure the same thing, so one can stand in for the other.
+    fn same_as(&self, other: &Self) -> bool {
+        (self.day, self.input, self.phase) == (other.day, other.input, other.phase)
+    }
+}
+
+fn summarize(day: u8, input: InputKind, phase: Phase, sorted: &[Duration]) -> Measurement {
+    Measurement {
+        day,
+        input,
+        phase,
+        samples: sorted.len(),
+        median: percentile(sorted, 50),
+        p95: percentile(sorted, 95),
+    }
+}
+
+fn bench_day(
+    puzzle: &Puzzle,
+    inputs: [&str; 2],
+    settings: &Settings,
+) -> Result<Vec<Measurement>, anyhow::Error> {
+    let mut measurements = vec![];
+    let kind = if settings.example {
+        InputKind::Example
+    } else {
+        InputKind::Real
+    };
+
+    let samples = measure(|| puzzle.parse(inputs[0]), settings);
+    measurements.push(summarize(puzzle.day, kind, Phase::Parse, &samples));
+
+    for (&part, input) in Part::ALL.iter().zip(inputs) {
+        if !puzzle.is_implemented(part) {
+            continue;
+        }
+
+        let problem = puzzle.parse(input)?;
+        // bail out on failing solvers rather than benchmark how fast they fail
+        puzzle
+            .solve(problem.as_ref(), part)
+            .with_context(|| format!("part {part} failed"))?;
+
+        let samples = measure(|| puzzle.solve(problem.as_ref(), part), settings);
+        measurements.push(summarize(puzzle.day, kind, Phase::Solve(part), &samples));
+    }
+
+    Ok(measurements)
+}
+
+fn duration_ns(d: Duration) -> Value {
+    Value::Integer(d.as_nanos().try_into().unwrap_or(i128::MAX))
+}
+
+fn to_json(measurements: &[Measurement]) -> Value {
+    let results = measurements
+        .iter()
+        .map(|m| {
+            Value::object([
+                ("day", Value::from(m.day)),
+                ("input", Value::from(m.input.to_string())),
+                ("phase", Value::from(m.phase.to_string())),
+                ("samples", Value::from(m.samples)),
+                ("median_ns", duration_ns(m.median)),
+                ("p95_ns", duration_ns(m.p95)),
+            ])
+        })
+        .collect();
+
+    Value::object([("results", Value::Array(results))])
+}
+
+fn from_json(v: &Value) -> Result<Vec<Measurement>, anyhow::Error> {
+    let results = v
+        .get("results")
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow!("missing results"))?;
+
+    results
+        .iter()
+        .map(|r| -> Result<Measurement, anyhow::Error> {
+            let field = |name| {
+                r.get(name)
+                    .ok_or_else(|| anyhow!("result is missing {:?}", name))
+            };
+            let integer = |name| -> Result<i128, anyhow::Error> {
+                field(name)?
+                    .as_i128()
+                    .ok_or_else(|| anyhow!("{:?} isn't an integer", name))
+            };
+            let duration = |name| -> Result<Duration, anyhow::Error> {
+                Ok(Duration::from_nanos(integer(name)?.try_into()?))
+            };
+
+            let input = field("input")?
+                .as_str()
+                .and_then(InputKind::from_name)
+                .ok_or_else(|| anyhow!("unknown input"))?;
+            let phase = field("phase")?
+                .as_str()
+                .and_then(Phase::from_name)
+                .ok_or_else(|| anyhow!("unknown phase"))?;
+
+            Ok(Measurement {
+                day: integer("day")?.try_into()?,
+                input,
+                phase,
+                samples: integer("samples")?.try_into()?,
+                median: duration("median_ns")?,
+                p95: duration("p95_ns")?,
+            })
+        })
+        .collect()
+}
+
+fn load_baseline(path: &str) -> Result<Vec<Measurement>, anyhow::Error> {
+    match fs::read_to_string(path) {
+        Ok(content) => {
+            from_json(&content.parse()?).with_context(|| format!("couldn't parse {path}"))
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e).with_context(|| format!("couldn't read {path}")),
+    }
+}
+
+/// Replaces measurements of `baseline` with those taken again, keeping all the others.
+fn merge(baseline: Vec<Measurement>, measurements: &[Measurement]) -> Vec<Measurement> {
+    let mut merged = baseline
+        .into_iter()
+        .filter(|b| !measurements.iter().any(|m| m.same_as(b)))
+        .chain(measurements.iter().cloned())
+        .collect::<Vec<_>>();
+
+    merged.sort_by_key(|m| (m.day, m.input, m.phase));
+    merged
+}
+
+/// Relative change of the median, in percent.
+#[allow(clippy::cast_precision_loss)]
+fn relative_change(current: &Measurement, baseline: &Measurement) -> f64 {
+    let current = current.median.as_nanos() as f64;
+    let baseline = baseline.median.as_nanos().max(1) as f64;
+
+    (current / baseline - 1.0) * 100.0
+}
+
+fn read_inputs(puzzle: &Puzzle, example: bool) -> Result<Option<[String; 2]>, anyhow::Error> {
+    if example {
+        return Ok(Some(puzzle.examples.map(str::to_string)));
+    }
+
+    let path = days::input_path(puzzle.day);
+
+    if !Path::new(&path).exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path).with_context(|| format!("couldn't read {path}"))?;
+
+    Ok(Some([content.clone(), content]))
+}
+
+/// Measures parsing and both parts of selected days, then compares medians with the stored
+/// baseline. Fails when any phase got slower by more than the threshold. Saving updates only the
+/// measured days in the baseline, and is refused after a regression unless forced.
+pub fn bench(args: &[String]) -> Result<(), anyhow::Error> {
+    let settings = Settings::parse(args)?;
+    let baseline = load_baseline(&settings.baseline)?;
+
+    let puzzles = if settings.days.is_empty() {
+        days::DAYS.iter().collect::<Vec<_>>()
+    } else {
+        settings
+            .days
+            .iter()
+            .map(|&d| days::find(d))
+            .collect::<Result<_, _>>()?
+    };
+
+    let mut measurements = vec![];
+    let mut failures = 0;
+
+    for puzzle in puzzles {
+        let Some(inputs) = read_inputs(puzzle, settings.example)? else {
+            eprintln!("Day {:02}: skipped, no input", puzzle.day);
+            continue;
+        };
+
+        match bench_day(puzzle, [&inputs[0], &inputs[1]], &settings) {
+            Ok(m) => measurements.extend(m),
+            Err(e) => {
+                eprintln!("Day {:02}: {:#}", puzzle.day, e);
+                failures += 1;
+            }
+        }
+    }
+
+    println!(
+        "{:<4} {:<7} {:>12} {:>12} {:>12} {:>9}",
+        "Day", "Phase", "Median", "p95", "Baseline", "Change"
+    );
+
+    let mut regressions = 0;
+
+    for m in &measurements {
+        let previous = baseline.iter().find(|b| b.same_as(m));
+
+        let (baseline_median, change) = previous.map_or_else(
+            || ("-".to_string(), "-".to_string()),
+            |b| {
+                (
+                    format!("{:?}", b.median),
+                    format!("{:+.1}%", relative_change(m, b)),
+                )
+            },
+        );
+
+        let regressed = previous.is_some_and(|b| relative_change(m, b) > settings.threshold);
+        if regressed {
+            regressions += 1;
+        }
+
+        println!(
+            "{:<4} {:<7} {:>12} {:>12} {:>12} {:>9}{}",
+            format!("{:02}", m.day),
+            m.phase.to_string(),
+            format!("{:?}", m.median),
+            format!("{:?}", m.p95),
+            baseline_median,
+            change,
+            if regressed { "  REGRESSION" } else { "" }
+        );
+    }
+
+    if settings.save && regressions > 0 && !settings.force {
+        eprintln!(
+            "Not saving {} over a regression, use --force to save anyway",
+            settings.baseline
+        );
+    } else if settings.save {
+        let merged = merge(baseline, &measurements);
+        fs::write(&settings.baseline, format!("{}\n", to_json(&merged)))
+            .with_context(|| format!("couldn't write {}", settings.baseline))?;
+    }
+
+    if failures > 0 {
+        bail!("{} day(s) failed", failures);
+    }
+
+    if regressions > 0 {
+        bail!(
+            "{} phase(s) regressed by more than {}%",
+            regressions,
+            settings.threshold
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_percentile() {
+        let samples = (1..=20).map(ms).collect::<Vec<_>>();
+        assert_eq!(percentile(&samples, 50), ms(10));
+        assert_eq!(percentile(&samples, 95), ms(19));
+        assert_eq!(percentile(&samples, 100), ms(20));
+
+        assert_eq!(percentile(&[ms(7)], 50), ms(7));
+        assert_eq!(percentile(&[ms(7)], 95), ms(7));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let measurements = vec![
+            Measurement {
+                day: 1,
+                input: InputKind::Example,
+                phase: Phase::Parse,
+                samples: 20,
+                median: Duration::from_nanos(1234),
+                p95: Duration::from_nanos(2345),
+            },
+            Measurement {
+                day: 12,
+                input: InputKind::Real,
+                phase: Phase::Solve(Part::Two),
+                samples: 5,
+                median: ms(3),
+                p95: ms(4),
+            },
+        ];
+
+        let json = to_json(&measurements).to_string();
+        assert!(json.contains(r#"{"day":12,"input":"input","phase":"part_2","samples":5,"#));
+        assert_eq!(from_json(&json.parse().unwrap()).unwrap(), measurements);
+
+        let missing =
+            r#"{"results":[{"day":3,"phase":"parse","samples":1,"median_ns":1,"p95_ns":1}]}"#;
+        assert!(from_json(&missing.parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_merge() {
+        let m = |day, input, median| Measurement {
+            day,
+            input,
+            phase: Phase::Parse,
+            samples: 1,
+            median: ms(median),
+            p95: ms(median),
+        };
+
+        let baseline = vec![
+            m(1, InputKind::Real, 10),
+            m(1, InputKind::Example, 1),
+            m(2, InputKind::Real, 20),
+        ];
+        assert_eq!(
+            merge(
+                baseline,
+                &[m(1, InputKind::Real, 12), m(3, InputKind::Real, 30)]
+            ),
+            vec![
+                m(1, InputKind::Example, 1),
+                m(1, InputKind::Real, 12),
+                m(2, InputKind::Real, 20),
+                m(3, InputKind::Real, 30),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_relative_change() {
+        let m = |median| Measurement {
+            day: 1,
+            input: InputKind::Real,
+            phase: Phase::Parse,
+            samples: 1,
+            median,
+            p95: median,
+        };
+
+        assert!((relative_change(&m(ms(12)), &m(ms(10))) - 20.0).abs() < 1e-9);
+        assert!((relative_change(&m(ms(5)), &m(ms(10))) + 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_settings_parsing() {
+        let args = [
+            "5",
+            "12",
+            "--samples",
+            "3",
+            "--save",
+            "--force",
+            "--threshold",
+            "2.5",
+        ]
+        .map(str::to_string);
+        let settings = Settings::parse(&args).unwrap();
+
+        assert_eq!(settings.days, vec![5, 12]);
+        assert_eq!(settings.samples, 3);
+        assert!(settings.save && settings.force);
+        assert!((settings.threshold - 2.5).abs() < f64::EPSILON);
+
+        assert!(Settings::parse(&["--samples".to_string(), "0".to_string()]).is_err());
+        assert!(Settings::parse(&["--fast".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_bench_day() {
+        let puzzle = days::find(1).unwrap();
+        let settings = Settings {
+            warmup: 0,
+            samples: 3,
+            ..Settings::default()
+        };
+
+        let measurements = bench_day(puzzle, puzzle.examples, &settings).unwrap();
+        let phases = measurements.iter().map(|m| m.phase).collect::<Vec<_>>();
+
+        assert_eq!(
+            phases,
+            vec![
+                Phase::Parse,
+                Phase::Solve(Part::One),
+                Phase::Solve(Part::Two)
+            ]
+        );
+        assert!(measurements.iter().all(|m| m.samples == 3));
+    }
+}
@@ -0,0 +1,56 @@
+use anyhow::anyhow;
+use aoc_core::Puzzle;
+
+/// Every day with a solution, in calendar order.
+pub const DAYS: &[Puzzle] = &[
+    Puzzle::of::<day_01::Day>(),
+    Puzzle::of::<day_02::Day>(),
+    Puzzle::of::<day_03::Day>(),
+    Puzzle::of::<day_04::Day>(),
+    Puzzle::of::<day_05::Day>(),
+    Puzzle::of::<day_06::Day>(),
+    Puzzle::of::<day_07::Day>(),
+    Puzzle::of::<day_08::Day>(),
+    Puzzle::of::<day_09::Day>(),
+    Puzzle::of::<day_10::Day>(),
+    Puzzle::of::<day_11::Day>(),
+    Puzzle::of::<day_12::Day>(),
+    Puzzle::of::<day_14::Day>(),
+    Puzzle::of::<day_19::Day>(),
+];
+
+/// # Errors
+///
+/// Returns error when the day has no solution.
+pub fn find(day: u8) -> Result<&'static Puzzle, anyhow::Error> {
+    DAYS.iter()
+        .find(|p| p.day == day)
+        .ok_or_else(|| anyhow!("day {} has no solution yet", day))
+}
+
+/// Conventional location of the puzzle input, relative to the directory the runner is started from.
+pub fn input_path(day: u8) -> String {
+    format!("day_{day:02}/input.txt")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_sorted_and_unique() {
+        assert!(DAYS.windows(2).all(|w| w[0].day < w[1].day));
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(5).unwrap().day, 5);
+        assert!(find(26).is_err());
+    }
+
+    #[test]
+    fn test_input_path() {
+        assert_eq!(input_path(5), "day_05/input.txt");
+        assert_eq!(input_path(19), "day_19/input.txt");
+    }
+}
@@ -0,0 +1,252 @@
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+};
+
+use anyhow::{bail, Context};
+use aoc::days;
+use aoc_core::{Answer, Part, Puzzle};
+
+use crate::{
+    arguments,
+    fuzz::{panic_message, silence_panics},
+};
+
+pub const USAGE: &str = "aoc diff [<day>...] [--seeds <n>] [--size <n>]";
+
+#[derive(Debug, PartialEq, Eq)]
+struct Settings {
+    days: Vec<u8>,
+    seeds: u64,
+    size: usize,
+}
+
+impl Settings {
+    fn parse(args: &[String]) -> Result<Self, anyhow::Error> {
+        let mut settings = Self {
+            days: vec![],
+            seeds: 100,
+            size: 10,
+        };
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seeds" => settings.seeds = arguments::value(&mut args, arg)?.parse()?,
+                "--size" => settings.size = arguments::value(&mut args, arg)?.parse()?,
+                day => settings.days.push(arguments::day(day, USAGE)?),
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+/// What a solver came up with. Any two errors agree, whatever they say, but a panic never agrees
+/// with anything.
+#[derive(Debug)]
+enum Outcome {
+    Answer(Answer),
+    Error(anyhow::Error),
+    Panic(String),
+}
+
+impl Outcome {
+    fn of(solve: impl FnOnce() -> Result<Answer, anyhow::Error>) -> Self {
+        match panic::catch_unwind(AssertUnwindSafe(solve)) {
+            Ok(Ok(answer)) => Self::Answer(answer),
+            Ok(Err(e)) => Self::Error(e),
+            Err(payload) => Self::Panic(panic_message(payload.as_ref())),
+        }
+    }
+
+    fn agrees_with(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Answer(a), Self::Answer(b)) => a == b,
+            (Self::Error(_), Self::Error(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Answer(answer) => write!(f, "{answer}"),
+            Self::Error(e) => write!(f, "error {e:#}"),
+            Self::Panic(message) => write!(f, "panic {message:?}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Disagreement {
+    part: Part,
+    seed: u64,
+    size: usize,
+    input: String,
+    solver: Outcome,
+    reference: Outcome,
+}
+
+/// Parts of a day with a reference solver, and the first input, if any, each of them disagrees
+/// with it on.
+#[derive(Debug, Default)]
+struct Report {
+    checked: Vec<Part>,
+    disagreements: Vec<Disagreement>,
+}
+
+/// Compares answers of the solver and of the reference on generated inputs. Sizes go round from 1
+/// up to `max_size`, so disagreements turn up on small inputs first.
+fn diff_day(puzzle: &Puzzle, seeds: u64, max_size: usize) -> Result<Report, anyhow::Error> {
+    let generator = aoc_gen::find(puzzle.day)?;
+    let mut report = Report::default();
+
+    for (seed, size) in (0..seeds).zip((1..=max_size.max(1)).cycle()) {
+        let input = generator.generate(seed, size);
+        let problem = puzzle.parse(&input).with_context(|| {
+            format!("generated input of seed {seed} and size {size} doesn't parse")
+        })?;
+
+        for &part in puzzle.implemented {
+            if report.disagreements.iter().any(|d| d.part == part) {
+                continue;
+            }
+
+            let reference = Outcome::of(|| puzzle.solve_reference(problem.as_ref(), part));
+
+            if matches!(reference, Outcome::Answer(Answer::Unimplemented)) {
+                continue;
+            }
+
+            if !report.checked.contains(&part) {
+                report.checked.push(part);
+            }
+
+            let solver = Outcome::of(|| puzzle.solve(problem.as_ref(), part));
+
+            if !solver.agrees_with(&reference) {
+                report.disagreements.push(Disagreement {
+                    part,
+                    seed,
+                    size,
+                    input: input.clone(),
+                    solver,
+                    reference,
+                });
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Checks every day (or the chosen ones) against its reference solvers on generated inputs,
+/// printing the first input they disagree on.
+pub fn diff(args: &[String]) -> Result<(), anyhow::Error> {
+    let settings = Settings::parse(args)?;
+
+    let puzzles = if settings.days.is_empty() {
+        days::DAYS.iter().collect()
+    } else {
+        settings
+            .days
+            .iter()
+            .map(|&day| days::find(day))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    // panics are reported along with the disagreements
+    let reports = silence_panics(|| {
+        puzzles
+            .iter()
+            .map(|puzzle| diff_day(puzzle, settings.seeds, settings.size))
+            .collect::<Vec<_>>()
+    });
+
+    let mut found = 0;
+
+    for (puzzle, report) in puzzles.iter().zip(reports) {
+        let report = report.with_context(|| format!("day {:02}", puzzle.day))?;
+
+        if report.checked.is_empty() {
+            println!("Day {:02}: no reference solver", puzzle.day);
+            continue;
+        }
+
+        println!(
+            "Day {:02}: {} of {} part(s) agree on {} inputs",
+            puzzle.day,
+            report.checked.len() - report.disagreements.len(),
+            report.checked.len(),
+            settings.seeds
+        );
+
+        for d in report.disagreements {
+            println!(
+                "    part {}: {} but the reference says {}, on seed {} and size {}:\n{}\n",
+                u8::from(d.part),
+                d.solver,
+                d.reference,
+                d.seed,
+                d.size,
+                d.input
+            );
+            found += 1;
+        }
+    }
+
+    if found > 0 {
+        bail!("found {} disagreement(s)", found);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_settings_parsing() {
+        let args = ["9", "--seeds", "20", "--size", "3"].map(str::to_string);
+        assert_eq!(
+            Settings::parse(&args).unwrap(),
+            Settings {
+                days: vec![9],
+                seeds: 20,
+                size: 3,
+            }
+        );
+        assert!(Settings::parse(&["--size".to_string()]).is_err());
+        assert!(Settings::parse(&["x".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_outcomes() {
+        let answer = |n: i32| Outcome::Answer(n.into());
+        let error = || Outcome::Error(anyhow!("no"));
+        let panic = || Outcome::Panic("oops".to_string());
+
+        assert!(answer(1).agrees_with(&answer(1)));
+        assert!(!answer(1).agrees_with(&answer(2)));
+        assert!(error().agrees_with(&error()));
+        assert!(!error().agrees_with(&answer(1)));
+        assert!(!panic().agrees_with(&panic()));
+    }
+
+    #[test]
+    fn test_solvers_agree_with_references() {
+        for puzzle in days::DAYS {
+            let report = diff_day(puzzle, 30, 4).unwrap();
+            assert!(
+                report.disagreements.is_empty(),
+                "day {:02}: {:#?}",
+                puzzle.day,
+                report.disagreements
+            );
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+use anyhow::{bail, Context};
+use aoc::days;
+use aoc_core::{Part, Puzzle};
+use aoc_gen::Rng;
+
+use crate::arguments;
+
+pub const USAGE: &str = "aoc fuzz [<day>...] [--iterations <n>] [--seed <n>] [--output <dir>]";
+
+#[derive(Debug, PartialEq, Eq)]
+struct Settings {
+    days: Vec<u8>,
+    iterations: usize,
+    seed: u64,
+    output: String,
+}
+
+impl Settings {
+    fn parse(args: &[String]) -> Result<Self, anyhow::Error> {
+        let mut settings = Self {
+            days: vec![],
+            iterations: 1000,
+            seed: 0,
+            output: "fuzz".to_string(),
+        };
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--iterations" => {
+                    settings.iterations = arguments::value(&mut args, arg)?.parse()?
+                }
+                "--seed" => settings.seed = arguments::value(&mut args, arg)?.parse()?,
+                "--output" => settings.output = arguments::value(&mut args, arg)?.clone(),
+                day => settings.days.push(arguments::day(day, USAGE)?),
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+pub(crate) fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Runs `f` with the panic hook silenced, for callers catching and reporting panics themselves,
+/// where the default hook would only add noise. The hook is put back however `f` ends, including
+/// when a panic escapes it.
+pub(crate) fn silence_panics<T>(f: impl FnOnce() -> T) -> T {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+
+    panic::set_hook(default_hook);
+
+    result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+/// Panic message, if parsing or solving any part of the input panics. Errors are fine, that's
+/// how bad input should be dealt with.
+fn run(puzzle: &Puzzle, input: &str) -> Option<String> {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        if let Ok(problem) = puzzle.parse(input) {
+            for part in Part::ALL {
+                let _ = puzzle.solve(problem.as_ref(), part);
+            }
+        }
+    }))
+    .err()
+    .map(|payload| panic_message(payload.as_ref()))
+}
+
+/// Runs mutations of the day's examples, returning the first input found for each distinct panic
+/// message. Same seed, same mutations.
+fn fuzz_day(puzzle: &Puzzle, seed: u64, iterations: usize) -> BTreeMap<String, String> {
+    let mut rng = Rng::new(seed ^ (u64::from(puzzle.day) << 32));
+    let mut panics = BTreeMap::new();
+
+    for _ in 0..iterations {
+        let example = *rng.choose(&puzzle.examples);
+        let input = aoc_gen::mutate(&mut rng, example);
+
+        if let Some(message) = run(puzzle, &input) {
+            panics.entry(message).or_insert(input);
+        }
+    }
+
+    panics
+}
+
+/// Feeds every day (or the chosen ones) mutated example inputs, looking for panics. Inputs
+/// causing them are saved to `--output`, one file per distinct panic.
+pub fn fuzz(args: &[String]) -> Result<(), anyhow::Error> {
+    let settings = Settings::parse(args)?;
+
+    let puzzles = if settings.days.is_empty() {
+        days::DAYS.iter().collect()
+    } else {
+        settings
+            .days
+            .iter()
+            .map(|&day| days::find(day))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    let results = silence_panics(|| {
+        puzzles
+            .iter()
+            .map(|puzzle| {
+                (
+                    puzzle.day,
+                    fuzz_day(puzzle, settings.seed, settings.iterations),
+                )
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let mut found = 0;
+
+    for (day, panics) in results {
+        println!(
+            "Day {:02}: {} distinct panic(s) in {} inputs",
+            day,
+            panics.len(),
+            settings.iterations
+        );
+
+        for (i, (message, input)) in panics.into_iter().enumerate() {
+            fs::create_dir_all(&settings.output)
+                .with_context(|| format!("couldn't create {}", settings.output))?;
+
+            let path = Path::new(&settings.output).join(format!("day_{day:02}-{i}.txt"));
+            fs::write(&path, &input)
+                .with_context(|| format!("couldn't write {}", path.display()))?;
+
+            println!("    {:?}, input saved to {}", message, path.display());
+            found += 1;
+        }
+    }
+
+    if found > 0 {
+        bail!("found {} distinct panic(s)", found);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_parsing() {
+        let args = ["5", "12", "--iterations", "50", "--seed", "3"].map(str::to_string);
+        assert_eq!(
+            Settings::parse(&args).unwrap(),
+            Settings {
+                days: vec![5, 12],
+                iterations: 50,
+                seed: 3,
+                output: "fuzz".to_string(),
+            }
+        );
+        assert!(Settings::parse(&["--seed".to_string()]).is_err());
+        assert!(Settings::parse(&["x".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_mutated_examples_dont_panic() {
+        for puzzle in days::DAYS {
+            let panics = fuzz_day(puzzle, 0, 200);
+            assert!(panics.is_empty(), "day {:02}: {:?}", puzzle.day, panics);
+        }
+    }
+}
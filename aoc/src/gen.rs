@@ -0,0 +1,82 @@
+use std::fs;
+
+use anyhow::{anyhow, bail, Context};
+
+use crate::arguments;
+
+pub const USAGE: &str = "aoc gen <day> [--seed <n>] [--size <n>] [--output <path>]";
+
+#[derive(Debug, PartialEq, Eq)]
+struct Settings {
+    day: u8,
+    seed: u64,
+    size: usize,
+    output: Option<String>,
+}
+
+impl Settings {
+    fn parse(args: &[String]) -> Result<Self, anyhow::Error> {
+        let mut day = None;
+        let mut seed = 0;
+        let mut size = 100;
+        let mut output = None;
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => seed = arguments::value(&mut args, arg)?.parse()?,
+                "--size" => size = arguments::value(&mut args, arg)?.parse()?,
+                "--output" => output = Some(arguments::value(&mut args, arg)?.clone()),
+                d if day.is_none() => day = Some(arguments::day(d, USAGE)?),
+                arg => bail!("unexpected argument {:?}\n\nUsage: {}", arg, USAGE),
+            }
+        }
+
+        let day = day.ok_or_else(|| anyhow!("missing day\n\nUsage: {}", USAGE))?;
+
+        Ok(Self {
+            day,
+            seed,
+            size,
+            output,
+        })
+    }
+}
+
+/// Prints a random input for the day, or writes it to `--output`. The same seed and size always
+/// give the same input.
+pub fn gen(args: &[String]) -> Result<(), anyhow::Error> {
+    let settings = Settings::parse(args)?;
+    let input = aoc_gen::find(settings.day)?.generate(settings.seed, settings.size);
+
+    match &settings.output {
+        Some(output) => {
+            fs::write(output, input + "\n").with_context(|| format!("couldn't write {output}"))?;
+        }
+        None => println!("{input}"),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_parsing() {
+        let args = ["12", "--seed", "7", "--output", "big.txt"].map(str::to_string);
+        assert_eq!(
+            Settings::parse(&args).unwrap(),
+            Settings {
+                day: 12,
+                seed: 7,
+                size: 100,
+                output: Some("big.txt".to_string()),
+            }
+        );
+        assert!(Settings::parse(&[]).is_err());
+        assert!(Settings::parse(&["5", "6"].map(str::to_string)).is_err());
+        assert!(Settings::parse(&["5", "--size"].map(str::to_string)).is_err());
+    }
+}
@@ -0,0 +1,261 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{anyhow, bail, Context};
+use aoc::days;
+use aoc_core::json::Value;
+
+use crate::arguments;
+
+pub const USAGE: &str = "aoc hyperfine [<day>...] [--warmup <n>] [--runs <n>] [--output <path>]";
+
+#[derive(Debug, PartialEq, Eq)]
+struct Settings {
+    days: Vec<u8>,
+    warmup: usize,
+    runs: Option<usize>,
+    output: Option<String>,
+}
+
+impl Settings {
+    fn parse(args: &[String]) -> Result<Self, anyhow::Error> {
+        let mut settings = Self {
+            days: vec![],
+            warmup: 3,
+            runs: None,
+            output: None,
+        };
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--warmup" => settings.warmup = arguments::value(&mut args, arg)?.parse()?,
+                "--runs" => settings.runs = Some(arguments::value(&mut args, arg)?.parse()?),
+                "--output" => settings.output = Some(arguments::value(&mut args, arg)?.clone()),
+                day => settings.days.push(arguments::day(day, USAGE)?),
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+/// Mean and standard deviation of a single benchmarked command, in seconds.
+#[derive(Debug, PartialEq)]
+struct Timing {
+    day: u8,
+    mean: f64,
+    stddev: f64,
+}
+
+/// Extracts the first result from hyperfine's `--export-json` output.
+fn parse_export(day: u8, export: &Value) -> Result<Timing, anyhow::Error> {
+    let result = export
+        .get("results")
+        .and_then(Value::as_array)
+        .and_then(<[Value]>::first)
+        .ok_or_else(|| anyhow!("hyperfine export has no results"))?;
+
+    let field = |name| {
+        result
+            .get(name)
+            .and_then(Value::as_f64)
+            .ok_or_else(|| anyhow!("hyperfine result is missing {:?}", name))
+    };
+
+    Ok(Timing {
+        day,
+        mean: field("mean")?,
+        // hyperfine reports null deviation for a single run
+        stddev: field("stddev").unwrap_or(0.0),
+    })
+}
+
+fn render_table(timings: &[Timing]) -> String {
+    let mut table = String::from("| Day | Mean [ms] | Std. dev. [ms] |\n|:---:|---:|---:|\n");
+
+    for Timing { day, mean, stddev } in timings {
+        table += &format!(
+            "| {:02} | {:.2} | {:.2} |\n",
+            day,
+            mean * 1000.0,
+            stddev * 1000.0
+        );
+    }
+
+    table
+}
+
+fn target_dir() -> PathBuf {
+    env::var_os("CARGO_TARGET_DIR").map_or_else(|| PathBuf::from("target"), PathBuf::from)
+}
+
+fn build_release() -> Result<(), anyhow::Error> {
+    // when started through `cargo run`, use the very same cargo
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+
+    let status = Command::new(cargo)
+        .args(["build", "--release", "--workspace", "--bins"])
+        .status()
+        .context("couldn't run cargo")?;
+
+    if !status.success() {
+        bail!("release build failed with {}", status);
+    }
+
+    Ok(())
+}
+
+fn run_hyperfine(day: u8, settings: &Settings) -> Result<Timing, anyhow::Error> {
+    let binary = target_dir()
+        .join("release")
+        .join(format!("day_{day:02}{}", env::consts::EXE_SUFFIX));
+    let export = env::temp_dir().join(format!("aoc-hyperfine-day_{day:02}.json"));
+
+    let mut command = Command::new("hyperfine");
+    command
+        .arg("--shell=none")
+        .args(["--warmup", &settings.warmup.to_string()])
+        .arg("--export-json")
+        .arg(&export)
+        .arg(format!("{} {}", binary.display(), days::input_path(day)));
+
+    if let Some(runs) = settings.runs {
+        command.args(["--runs", &runs.to_string()]);
+    }
+
+    let status = command
+        .status()
+        .context("couldn't run hyperfine, is it installed? (see .tool-versions)")?;
+
+    if !status.success() {
+        bail!("hyperfine failed with {}", status);
+    }
+
+    let content = fs::read_to_string(&export)
+        .with_context(|| format!("couldn't read {}", export.display()))?;
+    fs::remove_file(&export).ok();
+
+    parse_export(day, &content.parse()?)
+}
+
+/// Benchmarks release builds of day binaries end to end, including process startup and reading
+/// the input, and renders the results as a Markdown table fit for the README.
+pub fn hyperfine(args: &[String]) -> Result<(), anyhow::Error> {
+    let settings = Settings::parse(args)?;
+
+    let days = if settings.days.is_empty() {
+        days::DAYS.iter().map(|p| p.day).collect()
+    } else {
+        for &day in &settings.days {
+            days::find(day)?;
+        }
+        settings.days.clone()
+    };
+
+    build_release()?;
+
+    let mut timings = vec![];
+
+    for day in days {
+        if !Path::new(&days::input_path(day)).exists() {
+            eprintln!("Day {day:02}: skipped, no input");
+            continue;
+        }
+
+        timings.push(run_hyperfine(day, &settings).with_context(|| format!("day {day:02}"))?);
+    }
+
+    let table = render_table(&timings);
+    print!("{table}");
+
+    if let Some(output) = &settings.output {
+        fs::write(output, &table).with_context(|| format!("couldn't write {output}"))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // trimmed down output of `hyperfine --export-json`
+    const EXPORT: &str = r#"{
+  "results": [
+    {
+      "command": "target/release/day_05 day_05/input.txt",
+      "mean": 0.0012345,
+      "stddev": 0.0001,
+      "median": 0.0012,
+      "user": 0.0009,
+      "system": 0.0003,
+      "min": 0.0011,
+      "max": 0.0019,
+      "times": [0.0011, 0.0012, 0.0019],
+      "exit_codes": [0, 0, 0]
+    }
+  ]
+}"#;
+
+    #[test]
+    fn test_parse_export() {
+        let timing = parse_export(5, &EXPORT.parse().unwrap()).unwrap();
+        assert_eq!(
+            timing,
+            Timing {
+                day: 5,
+                mean: 0.001_234_5,
+                stddev: 0.0001
+            }
+        );
+
+        let single_run = r#"{"results":[{"mean":0.5,"stddev":null}]}"#;
+        assert!(parse_export(1, &single_run.parse().unwrap()).is_ok());
+        assert!(parse_export(1, &r#"{"results":[]}"#.parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_render_table() {
+        let timings = vec![
+            Timing {
+                day: 5,
+                mean: 0.001_234_5,
+                stddev: 0.0001,
+            },
+            Timing {
+                day: 12,
+                mean: 0.25,
+                stddev: 0.012_34,
+            },
+        ];
+
+        assert_eq!(
+            render_table(&timings),
+            "\
+| Day | Mean [ms] | Std. dev. [ms] |
+|:---:|---:|---:|
+| 05 | 1.23 | 0.10 |
+| 12 | 250.00 | 12.34 |
+"
+        );
+    }
+
+    #[test]
+    fn test_settings_parsing() {
+        let args = ["1", "3", "--runs", "10", "--output", "table.md"].map(str::to_string);
+        assert_eq!(
+            Settings::parse(&args).unwrap(),
+            Settings {
+                days: vec![1, 3],
+                warmup: 3,
+                runs: Some(10),
+                output: Some("table.md".to_string()),
+            }
+        );
+        assert!(Settings::parse(&["--runs".to_string()]).is_err());
+    }
+}
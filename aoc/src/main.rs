@@ -0,0 +1,237 @@
+use std::env;
+
+use anyhow::{bail, Context};
+use aoc::days;
+use aoc_core::{
+    cli::{self, Format, Input, Options},
+    Part,
+};
+
+mod all;
+mod answers;
+mod arguments;
+mod bench;
+mod diff;
+mod fuzz;
+mod gen;
+mod hyperfine;
+mod minimize;
+mod new;
+mod verify;
+
+const USAGE: &str = "\
+Usage:
+    aoc run <day> [<input> | - | --input <path>] [--part <1|2>] [--example] [--repeat <n>]
+                  [--time] [--stream] [--explain] [--format <text|json>]
+    aoc run --all [--part <1|2>] [--example] [--repeat <n>] [--time] [--format <text|json>]
+    aoc list
+    aoc verify [--record] [--answers <path>]
+    aoc bench [<day>...] [--example] [--warmup <n>] [--samples <n>] [--baseline <path>] [--save]
+              [--force] [--threshold <percent>]
+    aoc hyperfine [<day>...] [--warmup <n>] [--runs <n>] [--output <path>]
+    aoc gen <day> [--seed <n>] [--size <n>] [--output <path>]
+    aoc minimize <day> <input> (--panics | --errors | --command <cmd>) [--part <1|2>]
+                 [--output <path>]
+    aoc fuzz [<day>...] [--iterations <n>] [--seed <n>] [--output <dir>]
+    aoc diff [<day>...] [--seeds <n>] [--size <n>]
+    aoc new <day>
+
+Without an explicit input, day XX reads ./day_XX/input.txt.";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct RunArgs {
+    day: Option<u8>,
+    all: bool,
+    options: Options,
+}
+
+impl RunArgs {
+    fn parse(args: &[String]) -> Result<Self, anyhow::Error> {
+        let mut day = None;
+        let mut all = false;
+        let mut rest = Vec::with_capacity(args.len());
+
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--all" => all = true,
+                // values of these are left to `Options`, even when they look like a day
+                "--part" | "--repeat" | "--format" | "--input" => {
+                    rest.push(arg.clone());
+                    rest.extend(args.next().cloned());
+                }
+                d if day.is_none() && !all && d.parse::<u8>().is_ok() => {
+                    day = d.parse().ok();
+                }
+                _ => rest.push(arg.clone()),
+            }
+        }
+
+        let options = Options::parse(&rest).with_context(|| USAGE)?;
+
+        match (day, all) {
+            (None, false) => bail!("missing day\n\n{}", USAGE),
+            (Some(_), true) => bail!("can't combine a day with --all"),
+            (None, true) if options.stream => bail!("only some days can be streamed, not --all"),
+            (None, true) if options.explain => bail!("--explain only works for a single day"),
+            (None, true) if options.input.as_ref().is_some_and(|i| *i != Input::Example) => {
+                bail!("--all reads every day's own input, only --example can replace it")
+            }
+            _ => Ok(Self { day, all, options }),
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), anyhow::Error> {
+    let RunArgs { day, options, .. } = RunArgs::parse(args)?;
+
+    let Some(day) = day else {
+        return all::run_all(&options);
+    };
+
+    let puzzle = days::find(day)?;
+    let mut options = options;
+    options.input = options
+        .input
+        .or_else(|| Some(Input::Path(days::input_path(day))));
+
+    if options.format == Format::Text {
+        println!("Day {day:02}");
+    }
+
+    cli::run(puzzle, &options)
+}
+
+fn list() {
+    println!("Day  Part 1  Part 2");
+
+    for puzzle in days::DAYS {
+        let mark = |part| {
+            if puzzle.is_implemented(part) {
+                "yes"
+            } else {
+                "-"
+            }
+        };
+        println!(
+            "{:02}   {:<6}  {}",
+            puzzle.day,
+            mark(Part::One),
+            mark(Part::Two)
+        );
+    }
+}
+
+fn main() -> Result<(), anyhow::Error> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    match args.split_first() {
+        Some((command, rest)) if command == "run" => run(rest),
+        Some((command, rest)) if command == "verify" => verify::verify(rest),
+        Some((command, rest)) if command == "bench" => bench::bench(rest),
+        Some((command, rest)) if command == "hyperfine" => hyperfine::hyperfine(rest),
+        Some((command, rest)) if command == "gen" => gen::gen(rest),
+        Some((command, rest)) if command == "minimize" => minimize::minimize(rest),
+        Some((command, rest)) if command == "fuzz" => fuzz::fuzz(rest),
+        Some((command, rest)) if command == "diff" => diff::diff(rest),
+        Some((command, rest)) if command == "new" => new::new(rest),
+        Some((command, [])) if command == "list" => {
+            list();
+            Ok(())
+        }
+        _ => bail!("{}", USAGE),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_run_args_parsing() {
+        assert_eq!(
+            RunArgs::parse(&args("5 --part 2")).unwrap(),
+            RunArgs {
+                day: Some(5),
+                options: Options {
+                    part: Some(Part::Two),
+                    ..Options::default()
+                },
+                ..RunArgs::default()
+            }
+        );
+        assert_eq!(
+            RunArgs::parse(&args("--all --example")).unwrap(),
+            RunArgs {
+                all: true,
+                options: Options {
+                    input: Some(Input::Example),
+                    ..Options::default()
+                },
+                ..RunArgs::default()
+            }
+        );
+        assert_eq!(
+            RunArgs::parse(&args("12 other.txt")).unwrap().options.input,
+            Some(Input::Path("other.txt".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_run_args_flags_before_day() {
+        assert_eq!(
+            RunArgs::parse(&args("--repeat 2 5 --example")).unwrap(),
+            RunArgs {
+                day: Some(5),
+                options: Options {
+                    input: Some(Input::Example),
+                    repeat: 2,
+                    ..Options::default()
+                },
+                ..RunArgs::default()
+            }
+        );
+        assert_eq!(
+            RunArgs::parse(&args("--part 2 --input 3 7")).unwrap(),
+            RunArgs {
+                day: Some(7),
+                options: Options {
+                    input: Some(Input::Path("3".to_string())),
+                    part: Some(Part::Two),
+                    ..Options::default()
+                },
+                ..RunArgs::default()
+            }
+        );
+        assert_eq!(
+            RunArgs::parse(&args("--repeat 3 --all")).unwrap(),
+            RunArgs {
+                all: true,
+                options: Options {
+                    repeat: 3,
+                    ..Options::default()
+                },
+                ..RunArgs::default()
+            }
+        );
+        assert!(RunArgs::parse(&args("--repeat 3")).is_err());
+    }
+
+    #[test]
+    fn test_run_args_errors() {
+        assert!(RunArgs::parse(&args("")).is_err());
+        assert!(RunArgs::parse(&args("5 --all")).is_err());
+        assert!(RunArgs::parse(&args("5 --part 3")).is_err());
+        assert!(RunArgs::parse(&args("5 --part")).is_err());
+        assert!(RunArgs::parse(&args("--all x.txt")).is_err());
+        assert!(RunArgs::parse(&args("--all -")).is_err());
+        assert!(RunArgs::parse(&args("--all --stream")).is_err());
+        assert!(RunArgs::parse(&args("--all --explain")).is_err());
+        assert!(RunArgs::parse(&args("5 a.txt b.txt")).is_err());
+    }
+}
@@ -0,0 +1,379 @@
+use std::{
+    env, fs,
+    panic::{self, AssertUnwindSafe},
+    process::{self, Command, Stdio},
+};
+
+use anyhow::{anyhow, bail, Context};
+use aoc::days;
+use aoc_core::{Layout, Part, Puzzle};
+
+use crate::{arguments, fuzz::silence_panics};
+
+pub const USAGE: &str = "\
+aoc minimize <day> <input> (--panics | --errors | --command <cmd>) [--part <1|2>]
+             [--output <path>]";
+
+/// What has to keep happening for a smaller input to be accepted.
+#[derive(Debug, PartialEq, Eq)]
+enum Predicate {
+    /// Parsing or solving panics.
+    Panics,
+    /// Parsing or solving returns an error.
+    Errors,
+    /// The command, given path to the input as its last argument, exits with a failure. Handy for
+    /// comparing with another implementation.
+    Command(Vec<String>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Settings {
+    day: u8,
+    input: String,
+    predicate: Predicate,
+    part: Option<Part>,
+    output: String,
+}
+
+impl Settings {
+    fn parse(args: &[String]) -> Result<Self, anyhow::Error> {
+        let mut positional = vec![];
+        let mut predicates = vec![];
+        let mut part = None;
+        let mut output = "minimized.txt".to_string();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--panics" => predicates.push(Predicate::Panics),
+                "--errors" => predicates.push(Predicate::Errors),
+                "--command" => {
+                    let command = arguments::value(&mut args, arg)?
+                        .split_whitespace()
+                        .map(str::to_string)
+                        .collect();
+                    predicates.push(Predicate::Command(command));
+                }
+                "--part" => part = Some(arguments::value(&mut args, arg)?.parse()?),
+                "--output" => output = arguments::value(&mut args, arg)?.clone(),
+                arg => positional.push(arg),
+            }
+        }
+
+        let [day, input] = positional[..] else {
+            bail!("expected day and input\n\nUsage: {}", USAGE);
+        };
+
+        if predicates.len() > 1 {
+            bail!("only one of --panics, --errors and --command can be given");
+        }
+
+        let predicate = predicates
+            .pop()
+            .ok_or_else(|| anyhow!("missing predicate\n\nUsage: {}", USAGE))?;
+
+        if predicate == Predicate::Command(vec![]) {
+            bail!("empty command");
+        }
+
+        Ok(Self {
+            day: day
+                .parse()
+                .with_context(|| format!("invalid day {day:?}"))?,
+            input: input.to_string(),
+            predicate,
+            part,
+            output,
+        })
+    }
+}
+
+/// Smallest subsequence of `items` the predicate holds for, as far as removing ever smaller
+/// chunks of them goes. Starts with halves, so large irrelevant parts go away in few tries.
+fn shrink<T: Clone>(items: &[T], holds: &mut impl FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut items = items.to_vec();
+    let mut chunk = items.len().div_ceil(2).max(1);
+
+    loop {
+        let mut removed = false;
+        let mut start = 0;
+
+        while start < items.len() {
+            let end = (start + chunk).min(items.len());
+            let candidate = [&items[..start], &items[end..]].concat();
+
+            if holds(&candidate) {
+                items = candidate;
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+
+        match (chunk, removed) {
+            (1, false) => return items,
+            (1, true) => {}
+            _ => chunk = chunk.div_ceil(2),
+        }
+    }
+}
+
+fn sections(input: &str) -> Vec<Vec<&str>> {
+    input
+        .lines()
+        .collect::<Vec<_>>()
+        .split(|line| line.trim().is_empty())
+        .filter(|section| !section.is_empty())
+        .map(<[&str]>::to_vec)
+        .collect()
+}
+
+fn join_sections(sections: &[Vec<&str>]) -> String {
+    sections
+        .iter()
+        .map(|s| s.join("\n"))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn keep_columns(rows: &[&str], columns: &[usize]) -> Vec<String> {
+    rows.iter()
+        .map(|row| {
+            let cells = row.chars().collect::<Vec<_>>();
+            columns.iter().filter_map(|&j| cells.get(j)).collect()
+        })
+        .collect()
+}
+
+/// Removes whatever parts of the input `layout` allows, for as long as the predicate holds,
+/// until nothing more can go.
+fn shrink_input(layout: Layout, input: &str, holds: &mut impl FnMut(&str) -> bool) -> String {
+    match layout {
+        Layout::Lines => {
+            let lines = input.lines().collect::<Vec<_>>();
+
+            shrink(&lines, &mut |lines| holds(&lines.join("\n"))).join("\n")
+        }
+        Layout::Grid => {
+            let mut current = input.to_string();
+
+            loop {
+                let rows = current.lines().collect::<Vec<_>>();
+                let rows = shrink(&rows, &mut |rows| holds(&rows.join("\n")));
+
+                let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+                let columns = (0..width).collect::<Vec<_>>();
+                let columns = shrink(&columns, &mut |columns| {
+                    holds(&keep_columns(&rows, columns).join("\n"))
+                });
+
+                let next = keep_columns(&rows, &columns).join("\n");
+
+                if next == current {
+                    return current;
+                }
+
+                current = next;
+            }
+        }
+        Layout::Sections { header } => {
+            let mut current = sections(input);
+
+            loop {
+                let mut next = current.clone();
+
+                for k in 0..next.len() {
+                    let (head, body) = next[k].split_at(header.min(next[k].len()));
+                    let head = head.to_vec();
+
+                    // an emptied section would be gone altogether
+                    let body = shrink(body, &mut |body| {
+                        let mut candidate = next.clone();
+                        candidate[k] = [&head[..], body].concat();
+                        !candidate[k].is_empty() && holds(&join_sections(&candidate))
+                    });
+
+                    next[k] = [head, body].concat();
+                }
+
+                if next == current {
+                    return join_sections(&current);
+                }
+
+                current = next;
+            }
+        }
+    }
+}
+
+fn solve(puzzle: &Puzzle, input: &str, part: Option<Part>) -> Result<(), anyhow::Error> {
+    let problem = puzzle.parse(input)?;
+
+    for part in Part::ALL
+        .into_iter()
+        .filter(|&p| part.map_or(true, |q| q == p))
+    {
+        puzzle.solve(problem.as_ref(), part)?;
+    }
+
+    Ok(())
+}
+
+fn check(settings: &Settings, puzzle: &Puzzle, input: &str) -> Result<bool, anyhow::Error> {
+    match &settings.predicate {
+        Predicate::Panics => Ok(panic::catch_unwind(AssertUnwindSafe(|| {
+            let _ = solve(puzzle, input, settings.part);
+        }))
+        .is_err()),
+        Predicate::Errors => Ok(matches!(
+            panic::catch_unwind(AssertUnwindSafe(|| solve(puzzle, input, settings.part))),
+            Ok(Err(_))
+        )),
+        Predicate::Command(command) => {
+            let path = env::temp_dir().join(format!("aoc-minimize-{}.txt", process::id()));
+            fs::write(&path, input)
+                .with_context(|| format!("couldn't write {}", path.display()))?;
+
+            let status = Command::new(&command[0])
+                .args(&command[1..])
+                .arg(&path)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .with_context(|| format!("couldn't run {:?}", command[0]))?;
+
+            Ok(!status.success())
+        }
+    }
+}
+
+/// Shrinks an input the predicate holds for into a minimal reproducer, respecting the day's
+/// layout, and writes it to `--output`.
+pub fn minimize(args: &[String]) -> Result<(), anyhow::Error> {
+    let settings = Settings::parse(args)?;
+    let puzzle = days::find(settings.day)?;
+    let input = fs::read_to_string(&settings.input)
+        .with_context(|| format!("couldn't read {}", settings.input))?;
+
+    let mut tries = 0;
+    let mut error = None;
+
+    let result = silence_panics(|| {
+        let mut holds = |candidate: &str| {
+            tries += 1;
+
+            check(&settings, puzzle, candidate).unwrap_or_else(|e| {
+                error.get_or_insert(e);
+                false
+            })
+        };
+
+        if holds(&input) {
+            Ok(shrink_input(puzzle.layout, &input, &mut holds))
+        } else {
+            Err(anyhow!("predicate doesn't hold for {}", settings.input))
+        }
+    });
+
+    if let Some(e) = error {
+        return Err(e);
+    }
+
+    let minimized = result?;
+
+    fs::write(&settings.output, format!("{minimized}\n"))
+        .with_context(|| format!("couldn't write {}", settings.output))?;
+
+    println!(
+        "Shrunk {} lines down to {} in {} tries, written to {}",
+        input.lines().count(),
+        minimized.lines().count(),
+        tries,
+        settings.output
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_settings_parsing() {
+        assert_eq!(
+            Settings::parse(&args("12 big.txt --errors --part 2")).unwrap(),
+            Settings {
+                day: 12,
+                input: "big.txt".to_string(),
+                predicate: Predicate::Errors,
+                part: Some(Part::Two),
+                output: "minimized.txt".to_string(),
+            }
+        );
+        assert_eq!(
+            Settings::parse(&["5", "a.txt", "--command", "./other.sh -q"].map(str::to_string))
+                .unwrap()
+                .predicate,
+            Predicate::Command(args("./other.sh -q"))
+        );
+        assert!(Settings::parse(&args("12 big.txt")).is_err());
+        assert!(Settings::parse(&args("12 --panics")).is_err());
+        assert!(Settings::parse(&args("12 big.txt --panics --errors")).is_err());
+    }
+
+    #[test]
+    fn test_shrink() {
+        let items = (0..100).collect::<Vec<_>>();
+        let shrunk = shrink(&items, &mut |items| {
+            items.contains(&17) && items.contains(&42)
+        });
+        assert_eq!(shrunk, vec![17, 42]);
+
+        assert_eq!(shrink(&items, &mut |_| true), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_minimize_layouts() {
+        let lines = shrink_input(Layout::Lines, "a\nb\nc\nd", &mut |s| {
+            s.contains('b') && s.contains('d')
+        });
+        assert_eq!(lines, "b\nd");
+
+        let grid = shrink_input(Layout::Grid, "...\n.#.\n..#", &mut |s| {
+            s.contains('#')
+                && s.lines()
+                    .all(|l| l.len() == s.lines().next().unwrap().len())
+        });
+        assert_eq!(grid, "#");
+
+        // sections and their headers stay
+        let input = "seeds: 1\n\na map:\n1 2 3\n4 5 6\n\nb map:\n7 8 9";
+        assert_eq!(
+            shrink_input(Layout::Sections { header: 1 }, input, &mut |s| s
+                .contains('5')),
+            "seeds: 1\n\na map:\n4 5 6\n\nb map:"
+        );
+        assert_eq!(
+            shrink_input(Layout::Sections { header: 0 }, input, &mut |s| s
+                .contains('5')),
+            "seeds: 1\n\n4 5 6\n\n7 8 9"
+        );
+    }
+
+    #[test]
+    fn test_minimize_errors() {
+        let settings = Settings::parse(&args("9 input.txt --errors --part 1")).unwrap();
+        let puzzle = days::find(9).unwrap();
+        let input = "0 3 6 9 12 15\n1 2 4 8\n1 3 6 10 15 21";
+
+        let minimized = shrink_input(puzzle.layout, input, &mut |s| {
+            check(&settings, puzzle, s).unwrap()
+        });
+        assert_eq!(minimized, "1 2 4 8");
+    }
+}
@@ -0,0 +1,278 @@
+use std::{fs, path::Path};
+
+use anyhow::{anyhow, bail, Context};
+
+pub const USAGE: &str = "aoc new <day>";
+
+const CARGO_TOML: &str = include_str!("../templates/day/Cargo.toml");
+const LIB: &str = include_str!("../templates/day/lib.rs");
+const REFERENCE: &str = include_str!("../templates/day/reference.rs");
+const BIN: &str = include_str!("../templates/day/bin.rs");
+const GENERATOR: &str = include_str!("../templates/day/gen.rs");
+
+#[derive(Debug, PartialEq, Eq)]
+struct Settings {
+    day: u8,
+}
+
+impl Settings {
+    fn parse(args: &[String]) -> Result<Self, anyhow::Error> {
+        let [day] = args else {
+            bail!("expected a day\n\nUsage: {}", USAGE);
+        };
+
+        let day = day
+            .parse()
+            .ok()
+            .filter(|d| (1..=25).contains(d))
+            .ok_or_else(|| anyhow!("{:?} isn't a day of the advent calendar", day))?;
+
+        Ok(Self { day })
+    }
+}
+
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{padded_day}", &format!("{day:02}"))
+        .replace("{day}", &day.to_string())
+}
+
+// Every registration line names its crate, e.g. `day_05 = ...` or `Puzzle::of::<day_05::Day>()`.
+fn day_of(line: &str) -> Option<&str> {
+    let start = line.find("day_")?;
+    line.get(start..start + 6)
+}
+
+/// Inserts `entry` among the lines `is_entry` picks, keeping them ordered by day.
+fn register(
+    contents: &str,
+    entry: &str,
+    is_entry: impl Fn(&str) -> bool,
+) -> Result<String, anyhow::Error> {
+    let mut lines = contents.lines().collect::<Vec<_>>();
+    let entries = (0..lines.len())
+        .filter(|&i| is_entry(lines[i]))
+        .collect::<Vec<_>>();
+
+    let Some(&last) = entries.last() else {
+        bail!("couldn't find where to register {}", entry.trim());
+    };
+
+    if entries.iter().any(|&i| day_of(lines[i]) == day_of(entry)) {
+        bail!("{} is registered already", day_of(entry).unwrap_or(entry));
+    }
+
+    let at = entries
+        .iter()
+        .copied()
+        .find(|&i| day_of(lines[i]) > day_of(entry))
+        .unwrap_or(last + 1);
+    lines.insert(at, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Writes all `files` under `root`, or none of them: when one can't be written, those written
+/// before it get back their `previous` contents, or are removed if they had none.
+fn write_all(
+    root: &Path,
+    files: &[(String, String)],
+    previous: &[(&str, String)],
+) -> Result<(), anyhow::Error> {
+    for (i, (path, contents)) in files.iter().enumerate() {
+        if let Err(e) = fs::write(root.join(path), contents) {
+            for (written, _) in &files[..i] {
+                let _ = match previous.iter().find(|(p, _)| p == written) {
+                    Some((_, contents)) => fs::write(root.join(written), contents),
+                    None => fs::remove_file(root.join(written)),
+                };
+            }
+
+            return Err(e).with_context(|| format!("couldn't write {path}"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates the crate of the day under `root`, the workspace, and registers it with the runner and
+/// the input generators. Nothing is left behind unless all of it can be done.
+fn scaffold(root: &Path, day: u8) -> Result<(), anyhow::Error> {
+    let name = format!("day_{day:02}");
+    let dir = root.join(&name);
+    let generator = format!("aoc_gen/src/{name}.rs");
+
+    for path in [&dir, &root.join(&generator)] {
+        if path.exists() {
+            bail!("{} exists already", path.display());
+        }
+    }
+
+    let read = |path| {
+        fs::read_to_string(root.join(path))
+            .map(|contents| (path, contents))
+            .with_context(|| format!("couldn't read {path}"))
+    };
+    let previous = [
+        read("aoc/Cargo.toml")?,
+        read("aoc/src/days.rs")?,
+        read("aoc_gen/src/lib.rs")?,
+    ];
+    let [(_, cargo_toml), (_, days), (_, generators)] = &previous;
+
+    let edits = [
+        (
+            "aoc/Cargo.toml",
+            register(
+                cargo_toml,
+                &format!("{name} = {{ path = \"../{name}\" }}"),
+                |l| l.starts_with("day_"),
+            )?,
+        ),
+        (
+            "aoc/src/days.rs",
+            register(days, &format!("    Puzzle::of::<{name}::Day>(),"), |l| {
+                l.trim_start().starts_with("Puzzle::of::<")
+            })?,
+        ),
+        ("aoc_gen/src/lib.rs", {
+            let generators = register(
+                generators,
+                &format!("    Generator::new({day}, {name}::generate),"),
+                |l| l.trim_start().starts_with("Generator::new("),
+            )?;
+            register(&generators, &format!("mod {name};"), |l| {
+                l.starts_with("mod day_")
+            })?
+        }),
+    ];
+
+    let files = [
+        (format!("{name}/Cargo.toml"), render(CARGO_TOML, day)),
+        (format!("{name}/src/lib.rs"), render(LIB, day)),
+        (format!("{name}/src/reference.rs"), render(REFERENCE, day)),
+        (format!("{name}/src/bin/{name}.rs"), render(BIN, day)),
+        (generator, render(GENERATOR, day)),
+    ];
+
+    fs::create_dir_all(dir.join("src/bin"))
+        .with_context(|| format!("couldn't create {}", dir.display()))?;
+
+    let files = files
+        .into_iter()
+        .chain(edits.map(|(path, contents)| (path.to_string(), contents)))
+        .collect::<Vec<_>>();
+
+    write_all(root, &files, &previous).map_err(|e| {
+        let _ = fs::remove_dir_all(&dir);
+        e
+    })
+}
+
+/// Creates a crate for the day from a template, along with a stand-in input generator, and
+/// registers both. Refuses to touch a day that already exists.
+pub fn new(args: &[String]) -> Result<(), anyhow::Error> {
+    let Settings { day } = Settings::parse(args)?;
+
+    scaffold(Path::new("."), day)?;
+
+    println!(
+        "Created day_{day:02}, paste the example into its TEST_INPUT and the puzzle input into \
+         day_{day:02}/input.txt"
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use aoc::days;
+
+    use super::*;
+
+    #[test]
+    fn test_settings_parsing() {
+        assert_eq!(
+            Settings::parse(&["13".to_string()]).unwrap(),
+            Settings { day: 13 }
+        );
+        assert!(Settings::parse(&[]).is_err());
+        assert!(Settings::parse(&["26".to_string()]).is_err());
+        assert!(Settings::parse(&["0".to_string()]).is_err());
+        assert!(Settings::parse(&["5", "6"].map(str::to_string)).is_err());
+    }
+
+    #[test]
+    fn test_register() {
+        let contents = "[dependencies]\nanyhow = 1\nday_01 = 1\nday_14 = 1\n";
+        let is_entry = |l: &str| l.starts_with("day_");
+
+        assert_eq!(
+            register(contents, "day_05 = 1", is_entry).unwrap(),
+            "[dependencies]\nanyhow = 1\nday_01 = 1\nday_05 = 1\nday_14 = 1\n"
+        );
+        assert_eq!(
+            register(contents, "day_20 = 1", is_entry).unwrap(),
+            "[dependencies]\nanyhow = 1\nday_01 = 1\nday_14 = 1\nday_20 = 1\n"
+        );
+        assert!(register(contents, "day_14 = 2", is_entry).is_err());
+        assert!(register("", "day_14 = 2", is_entry).is_err());
+    }
+
+    #[test]
+    fn test_write_all() {
+        let root = env::temp_dir().join(format!("aoc-write-all-{}", process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("a"), "old").unwrap();
+
+        let files = [("a", "new"), ("b", "new"), ("missing/c", "new")]
+            .map(|(path, contents)| (path.to_string(), contents.to_string()));
+        let e = write_all(&root, &files, &[("a", "old".to_string())]).unwrap_err();
+        assert_eq!(e.to_string(), "couldn't write missing/c");
+
+        assert_eq!(fs::read_to_string(root.join("a")).unwrap(), "old");
+        assert!(!root.join("b").exists());
+
+        write_all(&root, &files[..2], &[]).unwrap();
+        assert_eq!(fs::read_to_string(root.join("b")).unwrap(), "new");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let root = env::temp_dir().join(format!("aoc-new-{}", process::id()));
+
+        for path in ["aoc/Cargo.toml", "aoc/src/days.rs", "aoc_gen/src/lib.rs"] {
+            fs::create_dir_all(root.join(path).parent().unwrap()).unwrap();
+            fs::copy(workspace.join(path), root.join(path)).unwrap();
+        }
+
+        let Some(day) = (1..=25).find(|&d| days::find(d).is_err()) else {
+            return;
+        };
+        let name = format!("day_{day:02}");
+        scaffold(&root, day).unwrap();
+
+        let lib = fs::read_to_string(root.join(format!("{name}/src/lib.rs"))).unwrap();
+        assert!(lib.contains(&format!("const DAY: u8 = {day};")));
+        let bin = fs::read_to_string(root.join(format!("{name}/src/bin/{name}.rs"))).unwrap();
+        assert!(bin.contains(&format!("{name}::Day")));
+
+        let days = fs::read_to_string(root.join("aoc/src/days.rs")).unwrap();
+        assert!(days.contains(&format!("    Puzzle::of::<{name}::Day>(),\n")));
+        let generators = fs::read_to_string(root.join("aoc_gen/src/lib.rs")).unwrap();
+        assert!(generators.contains(&format!("mod {name};\n")));
+        assert!(generators.contains(&format!("Generator::new({day}, {name}::generate),\n")));
+
+        // neither the new day nor an old one can be scaffolded again
+        assert!(scaffold(&root, day).is_err());
+        assert!(scaffold(&root, 5).is_err());
+        assert!(!root.join("day_05").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
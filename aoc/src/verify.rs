@@ -0,0 +1,150 @@
+use std::path::Path;
+
+use anyhow::bail;
+use aoc::days;
+use aoc_core::{
+    cli::{self, Input, Options},
+    Answer,
+};
+
+use crate::{answers, answers::Answers, arguments};
+
+pub const USAGE: &str = "aoc verify [--record] [--answers <path>]";
+
+#[derive(Debug, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail { expected: String, got: String },
+    Missing { got: String },
+}
+
+fn check(expected: Option<&str>, answer: &Result<Answer, anyhow::Error>) -> Status {
+    let got = match answer {
+        Ok(answer) => answer.to_string(),
+        Err(e) => format!("error: {e:#}"),
+    };
+
+    match expected {
+        Some(expected) if answer.is_ok() && expected == got => Status::Pass,
+        Some(expected) => Status::Fail {
+            expected: expected.to_string(),
+            got,
+        },
+        None => Status::Missing { got },
+    }
+}
+
+/// Runs every implemented part against the real input and compares answers with the recorded
+/// ones. With `--record`, answers that weren't recorded yet are saved; existing ones are never
+/// overwritten, so a regression can't sneak in as a new expected answer.
+pub fn verify(args: &[String]) -> Result<(), anyhow::Error> {
+    let mut record = false;
+    let mut path = answers::DEFAULT_PATH.to_string();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record" => record = true,
+            "--answers" => path = arguments::value(&mut args, arg)?.clone(),
+            arg => bail!("unexpected argument {:?}\n\nUsage: {}", arg, USAGE),
+        }
+    }
+
+    let mut answers = Answers::load(&path)?;
+    let (mut passed, mut failed, mut missing, mut recorded) = (0, 0, 0, 0);
+
+    for puzzle in days::DAYS {
+        let input_path = days::input_path(puzzle.day);
+
+        if !Path::new(&input_path).exists() {
+            println!("Day {:02}: skipped, no {}", puzzle.day, input_path);
+            continue;
+        }
+
+        let options = Options::default();
+        let report = match cli::execute(puzzle, &Input::Path(input_path), &options) {
+            Ok(report) => report,
+            Err(e) => {
+                println!("Day {:02}: FAIL, {:#}", puzzle.day, e);
+                failed += puzzle.implemented.len();
+                continue;
+            }
+        };
+
+        for part_report in report
+            .parts
+            .iter()
+            .filter(|r| puzzle.is_implemented(r.part))
+        {
+            let (day, part) = (puzzle.day, part_report.part);
+
+            match check(answers.get(day, part), &part_report.answer) {
+                Status::Pass => {
+                    println!("Day {day:02} part {part}: pass");
+                    passed += 1;
+                }
+                Status::Fail { expected, got } => {
+                    println!("Day {day:02} part {part}: FAIL, expected {expected}, got {got}");
+                    failed += 1;
+                }
+                Status::Missing { got } if record && part_report.answer.is_ok() => {
+                    println!("Day {day:02} part {part}: recorded {got}");
+                    answers.insert(day, part, got);
+                    recorded += 1;
+                }
+                Status::Missing { got } => {
+                    println!("Day {day:02} part {part}: missing, got {got}");
+                    missing += 1;
+                }
+            }
+        }
+    }
+
+    if recorded > 0 {
+        answers.save(&path)?;
+    }
+
+    println!("{passed} passed, {failed} failed, {missing} missing, {recorded} recorded");
+
+    if failed > 0 {
+        bail!("{} part(s) failed verification", failed);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_check() {
+        assert_eq!(check(Some("42"), &Ok(Answer::Integer(42))), Status::Pass);
+        assert_eq!(
+            check(Some("42"), &Ok(Answer::Integer(41))),
+            Status::Fail {
+                expected: "42".to_string(),
+                got: "41".to_string()
+            }
+        );
+        assert_eq!(
+            check(None, &Ok(Answer::Text("abc".to_string()))),
+            Status::Missing {
+                got: "abc".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_error_never_passes() {
+        let answer = Err(anyhow!("oops"));
+        assert_eq!(
+            check(Some("error: oops"), &answer),
+            Status::Fail {
+                expected: "error: oops".to_string(),
+                got: "error: oops".to_string()
+            }
+        );
+    }
+}
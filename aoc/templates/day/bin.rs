@@ -0,0 +1,3 @@
+fn main() -> Result<(), anyhow::Error> {
+    aoc_core::cli::main::<day_{padded_day}::Day>()
+}
@@ -0,0 +1,6 @@
+use crate::{lines, Rng};
+
+/// `size` lines of random numbers, standing in until the input of day {day} gets a shape.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| rng.range(0..=999u32).to_string()))
+}
@@ -0,0 +1,52 @@
+use std::str::FromStr;
+
+use aoc_core::{Part, Solution};
+use aoc_parse::Span;
+
+pub mod reference;
+
+#[derive(Debug)]
+pub struct Problem {
+    // left for the solvers, which are added along with the parts they solve
+    #[allow(dead_code)]
+    lines: Vec<String>,
+}
+
+impl FromStr for Problem {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = &aoc_parse::normalize(s);
+
+        let lines = Span::new(s)
+            .lines()
+            .map(|l| l.as_str().to_string())
+            .collect();
+
+        Ok(Self { lines })
+    }
+}
+
+const TEST_INPUT: &str = "\
+";
+
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = {day};
+    const IMPLEMENTED: &'static [Part] = &[];
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_parsing() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(p.lines.len(), TEST_INPUT.lines().count());
+    }
+}
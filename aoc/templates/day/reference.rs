@@ -0,0 +1 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
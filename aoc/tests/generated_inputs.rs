@@ -0,0 +1,76 @@
+//! Solves inputs from every day's generator, which must parse and solve without errors.
+
+use aoc::days::DAYS;
+use aoc_core::Part;
+
+const SEEDS: u64 = 20;
+const SIZES: [usize; 3] = [1, 5, 40];
+
+#[test]
+fn every_day_has_a_generator() {
+    for puzzle in DAYS {
+        assert!(aoc_gen::find(puzzle.day).is_ok(), "day {:02}", puzzle.day);
+    }
+}
+
+#[test]
+fn generated_inputs_are_solvable() {
+    let mut failures = vec![];
+
+    for puzzle in DAYS {
+        let generator = aoc_gen::find(puzzle.day).unwrap();
+
+        for seed in 0..SEEDS {
+            for size in SIZES {
+                let input = generator.generate(seed, size);
+
+                let result = puzzle.parse(&input).and_then(|problem| {
+                    Part::ALL
+                        .iter()
+                        .try_for_each(|&part| puzzle.solve(problem.as_ref(), part).map(drop))
+                });
+
+                if let Err(e) = result {
+                    failures.push(format!(
+                        "day {:02} failed with {:#} on seed {} and size {}",
+                        puzzle.day, e, seed, size
+                    ));
+                }
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn streamed_answers_match_parsed_ones() {
+    let mut failures = vec![];
+
+    for puzzle in DAYS {
+        let generator = aoc_gen::find(puzzle.day).unwrap();
+
+        for seed in 0..SEEDS {
+            for size in SIZES {
+                let input = generator.generate(seed, size);
+                let problem = puzzle.parse(&input).unwrap();
+
+                for part in Part::ALL {
+                    let Some(streamed) = puzzle.solve_stream(&mut input.as_bytes(), part) else {
+                        continue;
+                    };
+                    let parsed = puzzle.solve(problem.as_ref(), part).unwrap();
+
+                    if streamed.as_ref().ok() != Some(&parsed) {
+                        failures.push(format!(
+                            "day {:02} part {} streamed {:?} instead of {:?} on seed {} and size {}",
+                            puzzle.day, part, streamed, parsed, seed, size
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,118 @@
+//! Feeds every day a corpus of malformed inputs, derived from its examples, and checks that
+//! parsing and solving report errors instead of panicking.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use aoc::days::DAYS;
+use aoc_core::{Part, Puzzle};
+
+const GENERIC: &[&str] = &[
+    "",
+    " ",
+    "\n",
+    "\n\n\n",
+    "x",
+    "0",
+    "-1",
+    "99999999999999999999999999",
+    "\u{1F384}",
+    "\t\r\n",
+];
+
+// Characters each example character gets swapped for, one at a time.
+const REPLACEMENTS: &[&str] = &["", "0", "9", "-", " ", "\n", "x", "#", ".", "\u{e9}"];
+
+// Numbers overflowing or filling up common integer types.
+const HUGE_NUMBERS: &[&str] = &["18446744073709551615", "4294967295", "99999999999999999999"];
+
+fn corpus(puzzle: &Puzzle) -> Vec<String> {
+    let mut inputs = GENERIC.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    for example in puzzle.examples {
+        let boundaries = example
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([example.len()])
+            .collect::<Vec<_>>();
+
+        // truncated examples
+        inputs.extend(boundaries.iter().map(|&i| example[..i].to_string()));
+
+        // single character replacements
+        for window in boundaries.windows(2) {
+            let (before, after) = (&example[..window[0]], &example[window[1]..]);
+
+            inputs.extend(REPLACEMENTS.iter().map(|r| format!("{before}{r}{after}")));
+        }
+
+        // every line dropped or repeated in turn
+        let lines = example.lines().collect::<Vec<_>>();
+
+        for i in 0..lines.len() {
+            let mut dropped = lines.clone();
+            dropped.remove(i);
+            inputs.push(dropped.join("\n"));
+
+            let mut repeated = lines.clone();
+            repeated.insert(i, lines[i]);
+            inputs.push(repeated.join("\n"));
+        }
+
+        // numbers blown up one at a time
+        let numbers = example.match_indices(|c: char| c.is_ascii_digit());
+
+        for (i, _) in numbers {
+            let (before, after) = (&example[..i], &example[i..]);
+            let after = after.trim_start_matches(|c: char| c.is_ascii_digit());
+
+            inputs.extend(HUGE_NUMBERS.iter().map(|n| format!("{before}{n}{after}")));
+        }
+    }
+
+    inputs.sort_unstable();
+    inputs.dedup();
+    inputs
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[test]
+fn malformed_inputs_dont_panic() {
+    // panics are collected below, the default hook would only add noise
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut failures = vec![];
+
+    for puzzle in DAYS {
+        for input in corpus(puzzle) {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                let Ok(problem) = puzzle.parse(&input) else {
+                    return;
+                };
+
+                for part in Part::ALL {
+                    let _ = puzzle.solve(problem.as_ref(), part);
+                }
+            }));
+
+            if let Err(payload) = result {
+                failures.push(format!(
+                    "day {:02} panicked with {:?} on {:?}",
+                    puzzle.day,
+                    panic_message(payload.as_ref()),
+                    input
+                ));
+            }
+        }
+    }
+
+    let _ = panic::take_hook();
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
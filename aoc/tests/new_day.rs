@@ -0,0 +1,76 @@
+//! Scaffolds a day with `aoc new` into a copy of the workspace, which has to keep building and
+//! passing its tests.
+
+use std::{env, fs, io, path::Path, process::Command};
+
+use aoc::days;
+
+// Set for the copy, which runs this test too.
+const NESTED: &str = "AOC_NEW_DAY_COPY";
+
+fn copy_workspace(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name();
+
+        if name == "target" || name == ".git" {
+            continue;
+        }
+
+        if entry.file_type()?.is_dir() {
+            copy_workspace(&entry.path(), &to.join(&name))?;
+        } else {
+            fs::copy(entry.path(), to.join(&name))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_new_day_builds_and_passes_tests() {
+    if env::var_os(NESTED).is_some() {
+        return;
+    }
+
+    let Some(day) = (1..=25).find(|&d| days::find(d).is_err()) else {
+        return;
+    };
+
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let scratch = Path::new(env!("CARGO_TARGET_TMPDIR")).join("new_day");
+    let copy = scratch.join("workspace");
+
+    let _ = fs::remove_dir_all(&copy);
+    copy_workspace(&workspace, &copy).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["new", &day.to_string()])
+        .current_dir(&copy)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "aoc new {day} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // the target directory is kept between runs, so only the copied crates get rebuilt
+    let output = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .args(["test", "--workspace", "--offline"])
+        .current_dir(&copy)
+        .env("CARGO_TARGET_DIR", scratch.join("target"))
+        .env(NESTED, "1")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "tests failed after aoc new {day}:\n{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    fs::remove_dir_all(&copy).unwrap();
+}
@@ -0,0 +1,325 @@
+//! Heap allocation accounting, to see how much memory each phase of a solver goes through. The
+//! counting allocator is only installed with the `count-allocations` feature; without it every
+//! measurement comes out empty and costs nothing.
+
+use std::fmt;
+
+/// Whether allocations are being counted at all.
+pub const ENABLED: bool = cfg!(feature = "count-allocations");
+
+/// Allocations made while running a piece of code.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, reallocations included.
+    pub allocations: u64,
+    /// Bytes requested by all of them together.
+    pub bytes: u64,
+    /// Most bytes live at once, on top of what was live already.
+    pub peak: u64,
+}
+
+impl AllocStats {
+    /// Runs `f`, counting allocations it makes on the current thread.
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Self) {
+        let before = counting::snapshot();
+        counting::reset_peak();
+
+        let result = f();
+
+        let after = counting::snapshot();
+        counting::restore_peak(before.peak);
+
+        let stats = Self {
+            allocations: after.allocations - before.allocations,
+            bytes: after.bytes - before.bytes,
+            peak: u64::try_from(after.peak - before.live).unwrap_or(0),
+        };
+
+        (result, stats)
+    }
+
+    /// Adds up counts of both, keeping the higher peak.
+    pub fn merge(&mut self, other: Self) {
+        self.allocations += other.allocations;
+        self.bytes += other.bytes;
+        self.peak = self.peak.max(other.peak);
+    }
+
+    /// Counts of a single run out of `runs` merged together. Peak stays as it is.
+    #[must_use]
+    pub fn per_run(self, runs: u64) -> Self {
+        Self {
+            allocations: self.allocations / runs.max(1),
+            bytes: self.bytes / runs.max(1),
+            peak: self.peak,
+        }
+    }
+}
+
+// Counts again however it was before, even when the uncounted code panics.
+struct Resume(bool);
+
+impl Drop for Resume {
+    fn drop(&mut self) {
+        counting::set_paused(self.0);
+    }
+}
+
+/// Runs `f` without counting allocations it makes, for bookkeeping done on behalf of measured
+/// code that shouldn't be charged to it.
+pub fn uncounted<T>(f: impl FnOnce() -> T) -> T {
+    let _resume = Resume(counting::set_paused(true));
+    f()
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            Bytes(self.bytes),
+            Bytes(self.peak)
+        )
+    }
+}
+
+/// Byte count in the largest unit that keeps it at least 1.
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut value = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+
+        write!(f, "{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(feature = "count-allocations")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    #[derive(Clone, Copy)]
+    pub struct Counters {
+        pub allocations: u64,
+        pub bytes: u64,
+        pub live: i64,
+        pub peak: i64,
+    }
+
+    // Counted per thread, so solvers running side by side don't mix up their numbers. Memory
+    // freed on another thread than it was allocated on makes `live` drift, hence the signed type.
+    thread_local! {
+        static COUNTERS: Cell<Counters> = const {
+            Cell::new(Counters {
+                allocations: 0,
+                bytes: 0,
+                live: 0,
+                peak: 0,
+            })
+        };
+    }
+
+    thread_local! {
+        static PAUSED: Cell<bool> = const { Cell::new(false) };
+    }
+
+    pub fn set_paused(paused: bool) -> bool {
+        PAUSED.try_with(|p| p.replace(paused)).unwrap_or(false)
+    }
+
+    fn update(f: impl FnOnce(&mut Counters)) {
+        // const initialized and without drop glue, so it's accessible for the whole life of the
+        // thread and never allocates itself
+        let _ = COUNTERS.try_with(|counters| {
+            let mut c = counters.get();
+            f(&mut c);
+            counters.set(c);
+        });
+    }
+
+    fn record(allocated: usize, freed: usize) {
+        if PAUSED.try_with(Cell::get).unwrap_or(false) {
+            return;
+        }
+
+        let (allocated, freed) = (allocated as i64, freed as i64);
+
+        update(|c| {
+            if allocated > 0 {
+                c.allocations += 1;
+                c.bytes += allocated as u64;
+            }
+            c.live += allocated - freed;
+            c.peak = c.peak.max(c.live);
+        });
+    }
+
+    pub fn snapshot() -> Counters {
+        COUNTERS.with(Cell::get)
+    }
+
+    pub fn reset_peak() {
+        update(|c| c.peak = c.live);
+    }
+
+    pub fn restore_peak(peak: i64) {
+        update(|c| c.peak = c.peak.max(peak));
+    }
+
+    struct Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record(layout.size(), 0);
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record(layout.size(), 0);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record(0, layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                record(new_size, layout.size());
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+}
+
+#[cfg(not(feature = "count-allocations"))]
+mod counting {
+    #[derive(Clone, Copy)]
+    pub struct Counters {
+        pub allocations: u64,
+        pub bytes: u64,
+        pub live: i64,
+        pub peak: i64,
+    }
+
+    pub fn snapshot() -> Counters {
+        Counters {
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        }
+    }
+
+    pub fn reset_peak() {}
+
+    pub fn restore_peak(_: i64) {}
+
+    pub fn set_paused(_: bool) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let (v, stats) = AllocStats::measure(|| {
+            let scratch = vec![0u8; 4096];
+            drop(scratch);
+            vec![0u8; 1000]
+        });
+        assert_eq!(v.len(), 1000);
+
+        if ENABLED {
+            assert_eq!(stats.allocations, 2);
+            assert_eq!(stats.bytes, 5096);
+            assert_eq!(stats.peak, 4096);
+        } else {
+            assert_eq!(stats, AllocStats::default());
+        }
+    }
+
+    #[test]
+    fn test_uncounted() {
+        let (v, stats) = AllocStats::measure(|| {
+            let counted = vec![0u8; 100];
+            let uncounted = uncounted(|| vec![0u8; 1000]);
+            (counted, uncounted)
+        });
+        assert_eq!(v.1.len(), 1000);
+
+        if ENABLED {
+            assert_eq!(stats.allocations, 1);
+            assert_eq!(stats.bytes, 100);
+        } else {
+            assert_eq!(stats, AllocStats::default());
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut stats = AllocStats {
+            allocations: 3,
+            bytes: 300,
+            peak: 200,
+        };
+        stats.merge(AllocStats {
+            allocations: 1,
+            bytes: 100,
+            peak: 100,
+        });
+        assert_eq!(
+            stats.per_run(2),
+            AllocStats {
+                allocations: 2,
+                bytes: 200,
+                peak: 200,
+            }
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let stats = AllocStats {
+            allocations: 12,
+            bytes: 1536,
+            peak: 5 * 1024 * 1024,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "12 allocations, 1.5 KiB allocated, 5.0 MiB peak"
+        );
+        assert_eq!(Bytes(100).to_string(), "100 B");
+    }
+}
@@ -0,0 +1,731 @@
+//! Command line handling shared by every day binary and the `aoc` runner.
+
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Context};
+
+use crate::{
+    alloc::{self, AllocStats},
+    json::Value,
+    trace, Answer, Part, Puzzle, Solution,
+};
+
+pub const USAGE: &str = "\
+[<input> | - | --input <path>] [--part <1|2>] [--example] [--repeat <n>] [--time] [--stream]
+[--explain] [--format <text|json>]
+
+    <input>         path to the puzzle input, `-` reads it from stdin
+    --input <path>  same as <input>, for paths starting with `-`
+    --part <1|2>    solve only the given part
+    --example       solve the example from the puzzle description instead
+    --repeat <n>    solve <n> times, e.g. to give a profiler enough samples
+    --time          print how long parsing and each part took
+    --stream        solve reading the input a line at a time, in constant memory; only days
+                    with independent lines support it, and stdin can only be read once
+    --explain       print notes solvers leave on how they arrived at each answer
+    --format <fmt>  `text` for humans (default), `json` for a single line per day with
+                    answers, errors and timings of every phase";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Input {
+    Path(String),
+    Stdin,
+    Example,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            s => bail!("couldn't parse format from {:?}, expected text or json", s),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    pub input: Option<Input>,
+    pub part: Option<Part>,
+    pub repeat: usize,
+    pub time: bool,
+    pub stream: bool,
+    pub explain: bool,
+    pub format: Format,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            input: None,
+            part: None,
+            repeat: 1,
+            time: false,
+            stream: false,
+            explain: false,
+            format: Format::Text,
+        }
+    }
+}
+
+impl Options {
+    /// # Errors
+    ///
+    /// Returns error on unknown flags, missing or malformed flag values and conflicting inputs.
+    pub fn parse(args: &[String]) -> Result<Self, anyhow::Error> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+
+        let set_input = |options: &mut Self, input| {
+            if options.input.is_some() {
+                bail!("more than one input given");
+            }
+            options.input = Some(input);
+            Ok(())
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| anyhow!("missing value for {}", arg))
+            };
+
+            match arg.as_str() {
+                "--part" => options.part = Some(value()?.parse()?),
+                "--example" => set_input(&mut options, Input::Example)?,
+                "--repeat" => {
+                    let repeat = value()?;
+                    options.repeat = repeat
+                        .parse()
+                        .with_context(|| format!("couldn't parse repeat count from {repeat:?}"))?;
+                    if options.repeat == 0 {
+                        bail!("repeat count must be at least 1");
+                    }
+                }
+                "--time" => options.time = true,
+                "--stream" => options.stream = true,
+                "--explain" => options.explain = true,
+                "--format" => options.format = value()?.parse()?,
+                "-" => set_input(&mut options, Input::Stdin)?,
+                "--input" => set_input(&mut options, Input::Path(value()?.clone()))?,
+                path if !path.starts_with('-') => {
+                    set_input(&mut options, Input::Path(path.to_string()))?;
+                }
+                arg => bail!("unexpected argument {:?}", arg),
+            }
+        }
+
+        Ok(options)
+    }
+
+    #[must_use]
+    pub fn parts(&self) -> Vec<Part> {
+        self.part.map_or_else(|| Part::ALL.to_vec(), |p| vec![p])
+    }
+}
+
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: Part,
+    pub answer: Result<Answer, anyhow::Error>,
+    pub elapsed: Duration,
+    pub alloc: AllocStats,
+    /// Notes left by the solver, only collected with `--explain`.
+    pub trace: Vec<String>,
+}
+
+/// Outcome of solving a day, with durations and allocation counts averaged over all repetitions.
+#[derive(Debug)]
+pub struct Report {
+    pub day: u8,
+    pub repeat: usize,
+    pub parse: Duration,
+    pub parse_alloc: AllocStats,
+    pub parts: Vec<PartReport>,
+}
+
+impl Report {
+    /// # Errors
+    ///
+    /// Returns the first error reported by any of the parts.
+    pub fn check(&self) -> Result<(), anyhow::Error> {
+        for PartReport { part, answer, .. } in &self.parts {
+            if let Err(e) = answer {
+                bail!("part {} failed: {:#}", part, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Machine-readable form of the report. Field names are part of the output format, so
+    /// dashboards can rely on them.
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        let parts = self
+            .parts
+            .iter()
+            .map(
+                |PartReport {
+                     part,
+                     answer,
+                     elapsed,
+                     alloc,
+                     ..
+                 }| {
+                    let (status, answer, error) = match answer {
+                        Ok(Answer::Integer(n)) => ("ok", Value::Integer(*n), None),
+                        Ok(Answer::Text(s)) => ("ok", Value::from(s.as_str()), None),
+                        Ok(Answer::Unimplemented) => ("unimplemented", Value::Null, None),
+                        Err(e) => ("error", Value::Null, Some(format!("{e:#}"))),
+                    };
+
+                    Value::object([
+                        ("part", Value::from(u8::from(*part))),
+                        ("status", Value::from(status)),
+                        ("answer", answer),
+                        ("error", Value::from(error)),
+                        ("time_ns", duration_ns(*elapsed)),
+                        ("alloc", alloc_json(*alloc)),
+                    ])
+                },
+            )
+            .collect();
+
+        Value::object([
+            ("day", Value::from(self.day)),
+            ("repeat", Value::from(self.repeat)),
+            ("parse_ns", duration_ns(self.parse)),
+            ("parse_alloc", alloc_json(self.parse_alloc)),
+            ("error", Value::Null),
+            ("parts", Value::Array(parts)),
+        ])
+    }
+}
+
+fn duration_ns(d: Duration) -> Value {
+    Value::Integer(d.as_nanos().try_into().unwrap_or(i128::MAX))
+}
+
+// Null unless allocations are counted, rather than counts of zero nobody measured.
+fn alloc_json(stats: AllocStats) -> Value {
+    if !alloc::ENABLED {
+        return Value::Null;
+    }
+
+    Value::object([
+        ("allocations", Value::from(stats.allocations)),
+        ("bytes", Value::from(stats.bytes)),
+        ("peak", Value::from(stats.peak)),
+    ])
+}
+
+/// Machine-readable form of a day that couldn't even be parsed, shaped like
+/// [`Report::to_json`].
+#[must_use]
+pub fn failure_to_json(day: u8, options: &Options, error: &anyhow::Error) -> Value {
+    Value::object([
+        ("day", Value::from(day)),
+        ("repeat", Value::from(options.repeat)),
+        ("parse_ns", Value::Null),
+        ("parse_alloc", Value::Null),
+        ("error", Value::from(format!("{error:#}"))),
+        ("parts", Value::Array(vec![])),
+    ])
+}
+
+/// Reads the input from wherever it lives; examples are embedded, so there's nothing to read.
+fn read_input(input: &Input) -> Result<Option<String>, anyhow::Error> {
+    match input {
+        Input::Path(path) => fs::read_to_string(path)
+            .map(Some)
+            .with_context(|| format!("couldn't read {path}")),
+        Input::Stdin => {
+            let mut content = String::new();
+            io::stdin()
+                .read_to_string(&mut content)
+                .context("couldn't read stdin")?;
+            Ok(Some(content))
+        }
+        Input::Example => Ok(None),
+    }
+}
+
+/// Runs `f`, collecting notes it leaves only with `--explain`, so solvers don't bother with them
+/// otherwise.
+fn traced<T>(options: &Options, f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    if options.explain {
+        trace::collect(f)
+    } else {
+        (f(), vec![])
+    }
+}
+
+/// Opens the input for reading it as it goes; examples are read straight from memory.
+fn open_input(input: &Input, example: &'static str) -> Result<Box<dyn BufRead>, anyhow::Error> {
+    match input {
+        Input::Path(path) => {
+            let file = File::open(path).with_context(|| format!("couldn't read {path}"))?;
+            Ok(Box::new(BufReader::new(file)))
+        }
+        Input::Stdin => Ok(Box::new(io::stdin().lock())),
+        Input::Example => Ok(Box::new(example.as_bytes())),
+    }
+}
+
+/// Like [`execute`], but each part reads the input anew, solving it without ever holding all of
+/// it. There's no separate parsing, so its time goes into the parts'. Parts that can't be streamed
+/// are reported unimplemented.
+fn execute_stream(
+    puzzle: &Puzzle,
+    input: &Input,
+    options: &Options,
+) -> Result<Report, anyhow::Error> {
+    let parts = options.parts();
+
+    if *input == Input::Stdin && (parts.len() > 1 || options.repeat > 1) {
+        bail!("stdin can only be streamed once, choose a single --part and don't --repeat");
+    }
+
+    let mut report = Report {
+        day: puzzle.day,
+        repeat: options.repeat,
+        parse: Duration::ZERO,
+        parse_alloc: AllocStats::default(),
+        parts: vec![],
+    };
+
+    for part in parts {
+        let mut part_report = PartReport {
+            part,
+            answer: Ok(Answer::Unimplemented),
+            elapsed: Duration::ZERO,
+            alloc: AllocStats::default(),
+            trace: vec![],
+        };
+
+        for _ in 0..options.repeat {
+            let mut reader = open_input(input, puzzle.example(part))?;
+
+            let start = Instant::now();
+            let ((answer, alloc), trace) = traced(options, || {
+                AllocStats::measure(|| puzzle.solve_stream(reader.as_mut(), part))
+            });
+            part_report.elapsed += start.elapsed();
+            part_report.alloc.merge(alloc);
+            part_report.trace = trace;
+
+            part_report.answer = answer.unwrap_or(Ok(Answer::Unimplemented));
+        }
+
+        let repeat = u32::try_from(options.repeat)?;
+        part_report.elapsed /= repeat;
+        part_report.alloc = part_report.alloc.per_run(repeat.into());
+        report.parts.push(part_report);
+    }
+
+    Ok(report)
+}
+
+/// Parses and solves requested parts of `puzzle`. Parts that share an input are solved against
+/// a single parsed problem; in example mode each part may come with its own input. With
+/// `--stream` the parts read the input themselves instead.
+///
+/// # Errors
+///
+/// Returns error when the input can't be read or parsed. Failures of individual parts are
+/// recorded in the report instead.
+pub fn execute(puzzle: &Puzzle, input: &Input, options: &Options) -> Result<Report, anyhow::Error> {
+    if options.stream {
+        return execute_stream(puzzle, input, options);
+    }
+
+    let parts = options.parts();
+
+    let content = read_input(input)?;
+    let inputs = parts
+        .iter()
+        .map(|&part| content.as_deref().unwrap_or_else(|| puzzle.example(part)))
+        .collect::<Vec<_>>();
+
+    let mut report = Report {
+        day: puzzle.day,
+        repeat: options.repeat,
+        parse: Duration::ZERO,
+        parse_alloc: AllocStats::default(),
+        parts: parts
+            .iter()
+            .map(|&part| PartReport {
+                part,
+                answer: Ok(Answer::Unimplemented),
+                elapsed: Duration::ZERO,
+                alloc: AllocStats::default(),
+                trace: vec![],
+            })
+            .collect(),
+    };
+
+    for _ in 0..options.repeat {
+        let mut i = 0;
+
+        while i < inputs.len() {
+            let content = inputs[i];
+            let shared = inputs[i..].iter().take_while(|&&c| c == content).count();
+
+            let start = Instant::now();
+            let (problem, alloc) = AllocStats::measure(|| puzzle.parse(content));
+            report.parse += start.elapsed();
+            report.parse_alloc.merge(alloc);
+            let problem = problem?;
+
+            for part_report in &mut report.parts[i..(i + shared)] {
+                let start = Instant::now();
+                let ((answer, alloc), trace) = traced(options, || {
+                    AllocStats::measure(|| puzzle.solve(problem.as_ref(), part_report.part))
+                });
+                part_report.elapsed += start.elapsed();
+                part_report.answer = answer;
+                part_report.alloc.merge(alloc);
+                part_report.trace = trace;
+            }
+
+            i += shared;
+        }
+    }
+
+    let repeat = u32::try_from(options.repeat)?;
+    report.parse /= repeat;
+    report.parse_alloc = report.parse_alloc.per_run(repeat.into());
+    for part_report in &mut report.parts {
+        part_report.elapsed /= repeat;
+        part_report.alloc = part_report.alloc.per_run(repeat.into());
+    }
+
+    Ok(report)
+}
+
+pub fn print(report: &Report, options: &Options) {
+    for PartReport {
+        part,
+        answer,
+        trace,
+        ..
+    } in &report.parts
+    {
+        match answer {
+            Ok(answer) => println!("Part {part}: {answer}"),
+            Err(e) => println!("Part {part}: error: {e:#}"),
+        }
+
+        for note in trace {
+            println!("  {note}");
+        }
+    }
+
+    if options.time {
+        if report.repeat > 1 {
+            println!("Timings (mean of {} runs):", report.repeat);
+        } else {
+            println!("Timings:");
+        }
+
+        if alloc::ENABLED {
+            println!("  parse:  {:?}, {}", report.parse, report.parse_alloc);
+            for PartReport {
+                part,
+                elapsed,
+                alloc,
+                ..
+            } in &report.parts
+            {
+                println!("  part {part}: {elapsed:?}, {alloc}");
+            }
+        } else {
+            println!("  parse:  {:?}", report.parse);
+            for PartReport { part, elapsed, .. } in &report.parts {
+                println!("  part {part}: {elapsed:?}");
+            }
+        }
+    }
+}
+
+/// Solves `puzzle` according to `options` and prints the results.
+///
+/// # Errors
+///
+/// Returns error when no input was given, when the input can't be read or parsed, or when any
+/// of the parts fails. In JSON mode the failure is reported on stdout as well, so the error only
+/// serves to exit with a non-zero code.
+pub fn run(puzzle: &Puzzle, options: &Options) -> Result<(), anyhow::Error> {
+    let input = options
+        .input
+        .as_ref()
+        .ok_or_else(|| anyhow!("missing input, expected a path, `-` or --example"))?;
+
+    let report = execute(puzzle, input, options);
+
+    match options.format {
+        Format::Text => {
+            let report = report?;
+            print(&report, options);
+            report.check()
+        }
+        Format::Json => {
+            match &report {
+                Ok(report) => println!("{}", report.to_json()),
+                Err(e) => println!("{}", failure_to_json(puzzle.day, options, e)),
+            }
+            report?.check()
+        }
+    }
+}
+
+/// Entry point of a day binary.
+///
+/// # Errors
+///
+/// Returns error when arguments are invalid or solving fails, see [`run`].
+pub fn main<S: Solution>() -> Result<(), anyhow::Error>
+where
+    S::Problem: 'static,
+{
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let options =
+        Options::parse(&args).with_context(|| format!("usage: day_{:02} {}", S::DAY, USAGE))?;
+
+    run(&Puzzle::of::<S>(), &options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Doubling;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_options_parsing() {
+        assert_eq!(
+            Options::parse(&args("input.txt --part 2 --time")).unwrap(),
+            Options {
+                input: Some(Input::Path("input.txt".to_string())),
+                part: Some(Part::Two),
+                repeat: 1,
+                time: true,
+                stream: false,
+                explain: false,
+                format: Format::Text,
+            }
+        );
+        assert_eq!(
+            Options::parse(&args("--example --format json"))
+                .unwrap()
+                .format,
+            Format::Json
+        );
+        assert!(Options::parse(&args("input.txt --stream")).unwrap().stream);
+        assert!(
+            Options::parse(&args("--example --explain"))
+                .unwrap()
+                .explain
+        );
+        assert_eq!(
+            Options::parse(&args("- --repeat 10")).unwrap(),
+            Options {
+                input: Some(Input::Stdin),
+                repeat: 10,
+                ..Options::default()
+            }
+        );
+        assert_eq!(
+            Options::parse(&args("--example")).unwrap().input,
+            Some(Input::Example)
+        );
+        assert_eq!(
+            Options::parse(&args("--input other.txt")).unwrap().input,
+            Some(Input::Path("other.txt".to_string()))
+        );
+        assert_eq!(Options::parse(&args("")).unwrap(), Options::default());
+    }
+
+    #[test]
+    fn test_options_errors() {
+        assert!(Options::parse(&args("a.txt b.txt")).is_err());
+        assert!(Options::parse(&args("a.txt --example")).is_err());
+        assert!(Options::parse(&args("--part 3")).is_err());
+        assert!(Options::parse(&args("--repeat 0")).is_err());
+        assert!(Options::parse(&args("--repeat")).is_err());
+        assert!(Options::parse(&args("--verbose")).is_err());
+        assert!(Options::parse(&args("--format yaml")).is_err());
+    }
+
+    #[test]
+    fn test_execute_example() {
+        let puzzle = Puzzle::of::<Doubling>();
+        let options = Options {
+            repeat: 3,
+            ..Options::default()
+        };
+
+        let report = execute(&puzzle, &Input::Example, &options).unwrap();
+        assert_eq!(report.day, 1);
+        assert_eq!(report.repeat, 3);
+        assert_eq!(report.parts.len(), 2);
+        assert_eq!(report.parts[0].part, Part::One);
+        assert_eq!(
+            *report.parts[0].answer.as_ref().unwrap(),
+            Answer::Integer(42)
+        );
+        assert_eq!(
+            *report.parts[1].answer.as_ref().unwrap(),
+            Answer::Unimplemented
+        );
+        assert!(report.check().is_ok());
+    }
+
+    #[test]
+    fn test_report_to_json() {
+        let report = Report {
+            day: 5,
+            repeat: 1,
+            parse: Duration::from_nanos(1500),
+            parse_alloc: AllocStats::default(),
+            parts: vec![
+                PartReport {
+                    part: Part::One,
+                    answer: Ok(Answer::Integer(35)),
+                    elapsed: Duration::from_nanos(20),
+                    alloc: AllocStats::default(),
+                    trace: vec![],
+                },
+                PartReport {
+                    part: Part::Two,
+                    answer: Err(anyhow!("couldn't find min")),
+                    elapsed: Duration::from_nanos(30),
+                    alloc: AllocStats::default(),
+                    trace: vec![],
+                },
+            ],
+        };
+
+        let none = alloc_json(AllocStats::default());
+        assert_eq!(
+            report.to_json().to_string(),
+            format!(
+                concat!(
+                    r#"{{"day":5,"repeat":1,"parse_ns":1500,"parse_alloc":{none},"error":null,"#,
+                    r#""parts":[{{"part":1,"status":"ok","answer":35,"error":null,"time_ns":20,"#,
+                    r#""alloc":{none}}},{{"part":2,"status":"error","answer":null,"#,
+                    r#""error":"couldn't find min","time_ns":30,"alloc":{none}}}]}}"#
+                ),
+                none = none
+            )
+        );
+
+        let alloc = AllocStats {
+            allocations: 3,
+            bytes: 120,
+            peak: 80,
+        };
+        if alloc::ENABLED {
+            assert_eq!(
+                alloc_json(alloc).to_string(),
+                r#"{"allocations":3,"bytes":120,"peak":80}"#
+            );
+        } else {
+            assert_eq!(alloc_json(alloc), Value::Null);
+        }
+        assert!(report.check().is_err());
+    }
+
+    #[test]
+    fn test_failure_to_json() {
+        let e = anyhow!("no seeds");
+        assert_eq!(
+            failure_to_json(5, &Options::default(), &e).to_string(),
+            r#"{"day":5,"repeat":1,"parse_ns":null,"parse_alloc":null,"error":"no seeds","parts":[]}"#
+        );
+    }
+
+    #[test]
+    fn test_execute_explain() {
+        let puzzle = Puzzle::of::<Doubling>();
+        let mut options = Options {
+            part: Some(Part::One),
+            ..Options::default()
+        };
+
+        let report = execute(&puzzle, &Input::Example, &options).unwrap();
+        assert!(report.parts[0].trace.is_empty());
+
+        options.explain = true;
+        let report = execute(&puzzle, &Input::Example, &options).unwrap();
+        assert_eq!(report.parts[0].trace, ["doubling 21"]);
+    }
+
+    #[test]
+    fn test_execute_stream() {
+        let puzzle = Puzzle::of::<Doubling>();
+        let options = Options {
+            part: Some(Part::One),
+            repeat: 3,
+            stream: true,
+            ..Options::default()
+        };
+
+        let report = execute(&puzzle, &Input::Example, &options).unwrap();
+        assert_eq!(report.parse, Duration::ZERO);
+        assert_eq!(
+            *report.parts[0].answer.as_ref().unwrap(),
+            Answer::Integer(42)
+        );
+
+        assert!(execute(&puzzle, &Input::Stdin, &options).is_err());
+    }
+
+    #[test]
+    fn test_execute_stream_single_part() {
+        // Doubling streams only part 1
+        let puzzle = Puzzle::of::<Doubling>();
+        let options = Options {
+            stream: true,
+            ..Options::default()
+        };
+
+        let report = execute(&puzzle, &Input::Example, &options).unwrap();
+        assert_eq!(
+            report
+                .parts
+                .iter()
+                .map(|r| r.answer.as_ref().unwrap())
+                .collect::<Vec<_>>(),
+            [&Answer::Integer(42), &Answer::Unimplemented]
+        );
+        assert!(report.check().is_ok());
+    }
+
+    #[test]
+    fn test_execute_missing_file() {
+        let puzzle = Puzzle::of::<Doubling>();
+        let input = Input::Path("/nonexistent/input.txt".to_string());
+
+        assert!(execute(&puzzle, &input, &Options::default()).is_err());
+    }
+}
@@ -0,0 +1,456 @@
+//! Just enough JSON to produce machine-readable reports without pulling in a serialization
+//! framework.
+
+use std::{
+    fmt::{self, Write},
+    iter::Peekable,
+    str::{CharIndices, FromStr},
+};
+
+use anyhow::{anyhow, bail};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Integer(i128),
+    Float(f64),
+    String(String),
+    Array(Vec<Value>),
+    /// Fields keep their insertion order, so output is stable between runs.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn object<K: Into<String>>(fields: impl IntoIterator<Item = (K, Value)>) -> Self {
+        Self::Object(fields.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    /// Field of an object, `None` for missing fields and non-objects.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Self::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Self::Integer(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Numeric value, whether it was written as an integer or not.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Integer(n) => Some(*n as f64),
+            Self::Float(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Self::Bool(b)
+    }
+}
+
+impl From<i128> for Value {
+    fn from(n: i128) -> Self {
+        Self::Integer(n)
+    }
+}
+
+impl From<u8> for Value {
+    fn from(n: u8) -> Self {
+        Self::Integer(n.into())
+    }
+}
+
+impl From<u64> for Value {
+    fn from(n: u64) -> Self {
+        Self::Integer(n.into())
+    }
+}
+
+impl From<usize> for Value {
+    fn from(n: usize) -> Self {
+        Self::Integer(n as i128)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(n: f64) -> Self {
+        Self::Float(n)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Self::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Self::String(s)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(o: Option<T>) -> Self {
+        o.map_or(Self::Null, Into::into)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(v: Vec<T>) -> Self {
+        Self::Array(v.into_iter().map(Into::into).collect())
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", u32::from(c))?,
+            c => f.write_char(c)?,
+        }
+    }
+
+    f.write_char('"')
+}
+
+/// Compact, single-line JSON.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str("null"),
+            Self::Bool(b) => write!(f, "{b}"),
+            Self::Integer(n) => write!(f, "{n}"),
+            Self::Float(n) if n.is_finite() => write!(f, "{n}"),
+            // JSON has no representation for NaN or infinities
+            Self::Float(_) => f.write_str("null"),
+            Self::String(s) => write_string(f, s),
+            Self::Array(values) => {
+                f.write_char('[')?;
+                for (i, v) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{v}")?;
+                }
+                f.write_char(']')
+            }
+            Self::Object(fields) => {
+                f.write_char('{')?;
+                for (i, (k, v)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, k)?;
+                    write!(f, ":{v}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    s: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        while self
+            .chars
+            .next_if(|(_, c)| matches!(c, ' ' | '\t' | '\n' | '\r'))
+            .is_some()
+        {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), anyhow::Error> {
+        match self.chars.next() {
+            Some((_, c)) if c == expected => Ok(()),
+            Some((i, c)) => bail!("expected {:?} at byte {}, found {:?}", expected, i, c),
+            None => bail!("expected {:?}, found end of input", expected),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Value) -> Result<Value, anyhow::Error> {
+        for expected in keyword.chars() {
+            self.expect(expected)?;
+        }
+
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Value, anyhow::Error> {
+        self.skip_whitespace();
+
+        let value = match self.chars.peek() {
+            Some((_, '{')) => self.object()?,
+            Some((_, '[')) => self.array()?,
+            Some((_, '"')) => Value::String(self.string()?),
+            Some((_, 't')) => self.keyword("true", Value::Bool(true))?,
+            Some((_, 'f')) => self.keyword("false", Value::Bool(false))?,
+            Some((_, 'n')) => self.keyword("null", Value::Null)?,
+            Some((_, c)) if *c == '-' || c.is_ascii_digit() => self.number()?,
+            Some(&(i, c)) => bail!("unexpected {:?} at byte {}", c, i),
+            None => bail!("unexpected end of input"),
+        };
+
+        self.skip_whitespace();
+
+        Ok(value)
+    }
+
+    fn object(&mut self) -> Result<Value, anyhow::Error> {
+        self.expect('{')?;
+        self.skip_whitespace();
+
+        let mut fields = vec![];
+
+        if self.chars.next_if(|(_, c)| *c == '}').is_some() {
+            return Ok(Value::Object(fields));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            fields.push((key, self.value()?));
+
+            match self.chars.next() {
+                Some((_, ',')) => {}
+                Some((_, '}')) => return Ok(Value::Object(fields)),
+                Some((i, c)) => bail!("expected ',' or '}}' at byte {}, found {:?}", i, c),
+                None => bail!("unterminated object"),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, anyhow::Error> {
+        self.expect('[')?;
+        self.skip_whitespace();
+
+        let mut values = vec![];
+
+        if self.chars.next_if(|(_, c)| *c == ']').is_some() {
+            return Ok(Value::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+
+            match self.chars.next() {
+                Some((_, ',')) => {}
+                Some((_, ']')) => return Ok(Value::Array(values)),
+                Some((i, c)) => bail!("expected ',' or ']' at byte {}, found {:?}", i, c),
+                None => bail!("unterminated array"),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, anyhow::Error> {
+        self.expect('"')?;
+
+        let mut s = String::new();
+
+        loop {
+            match self.chars.next() {
+                Some((_, '"')) => return Ok(s),
+                Some((_, '\\')) => {
+                    let c = match self.chars.next() {
+                        Some((_, '"')) => '"',
+                        Some((_, '\\')) => '\\',
+                        Some((_, '/')) => '/',
+                        Some((_, 'b')) => '\u{8}',
+                        Some((_, 'f')) => '\u{c}',
+                        Some((_, 'n')) => '\n',
+                        Some((_, 'r')) => '\r',
+                        Some((_, 't')) => '\t',
+                        Some((_, 'u')) => {
+                            let hex = (0..4)
+                                .map(|_| self.chars.next().map(|(_, c)| c))
+                                .collect::<Option<String>>()
+                                .ok_or_else(|| anyhow!("unterminated unicode escape"))?;
+                            // surrogate pairs never show up in what we read, so they're
+                            // replaced rather than combined
+                            char::from_u32(u32::from_str_radix(&hex, 16)?)
+                                .unwrap_or(char::REPLACEMENT_CHARACTER)
+                        }
+                        Some((i, c)) => bail!("unknown escape {:?} at byte {}", c, i),
+                        None => bail!("unterminated string"),
+                    };
+                    s.push(c);
+                }
+                Some((_, c)) => s.push(c),
+                None => bail!("unterminated string"),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, anyhow::Error> {
+        let start = self.chars.peek().map_or(self.s.len(), |&(i, _)| i);
+
+        while self
+            .chars
+            .next_if(|(_, c)| matches!(c, '-' | '+' | '.' | 'e' | 'E' | '0'..='9'))
+            .is_some()
+        {}
+
+        let end = self.chars.peek().map_or(self.s.len(), |&(i, _)| i);
+        let number = &self.s[start..end];
+
+        if number.contains(['.', 'e', 'E']) {
+            Ok(Value::Float(number.parse()?))
+        } else {
+            Ok(Value::Integer(number.parse()?))
+        }
+    }
+}
+
+impl FromStr for Value {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            s,
+            chars: s.char_indices().peekable(),
+        };
+
+        let value = parser.value()?;
+
+        if let Some((i, c)) = parser.chars.next() {
+            bail!("trailing {:?} at byte {}", c, i);
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let v = Value::object([
+            ("day", Value::from(5u8)),
+            ("answer", Value::Integer(-35)),
+            ("ratio", Value::Float(0.5)),
+            ("error", Value::Null),
+            ("ok", Value::Bool(true)),
+            ("parts", Value::from(vec!["a", "b"])),
+        ]);
+
+        assert_eq!(
+            v.to_string(),
+            r#"{"day":5,"answer":-35,"ratio":0.5,"error":null,"ok":true,"parts":["a","b"]}"#
+        );
+    }
+
+    #[test]
+    fn test_string_escaping() {
+        assert_eq!(
+            Value::from("a \"quoted\"\\path\nnext\u{1}").to_string(),
+            r#""a \"quoted\"\\path\nnext\u0001""#
+        );
+        assert_eq!(Value::from("zażółć").to_string(), "\"zażółć\"");
+    }
+
+    #[test]
+    fn test_parsing() {
+        let v: Value = r#" {
+            "results": [
+                {"command": "day_01", "mean": 0.0012, "times": [1e-3, 2], "exit_codes": [0]},
+                {"command": "say \"hi\"\n", "ok": true, "missing": null}
+            ],
+            "count": -2
+        } "#
+        .parse()
+        .unwrap();
+
+        let results = v.get("results").and_then(Value::as_array).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[0].get("command").and_then(Value::as_str),
+            Some("day_01")
+        );
+        assert_eq!(results[0].get("mean").and_then(Value::as_f64), Some(0.0012));
+        assert_eq!(
+            results[0].get("times"),
+            Some(&Value::Array(vec![Value::Float(1e-3), Value::Integer(2)]))
+        );
+        assert_eq!(
+            results[1].get("command").and_then(Value::as_str),
+            Some("say \"hi\"\n")
+        );
+        assert_eq!(results[1].get("ok"), Some(&Value::Bool(true)));
+        assert_eq!(results[1].get("missing"), Some(&Value::Null));
+        assert_eq!(v.get("count").and_then(Value::as_i128), Some(-2));
+        assert_eq!(v.get("nope"), None);
+    }
+
+    #[test]
+    fn test_parsing_round_trip() {
+        let v = Value::object([
+            ("s", Value::from("tab\tquote\"\u{1}zażółć")),
+            ("a", Value::Array(vec![])),
+            ("o", Value::Object(vec![])),
+            ("f", Value::Float(-2.5)),
+        ]);
+
+        assert_eq!(v.to_string().parse::<Value>().unwrap(), v);
+    }
+
+    #[test]
+    fn test_parsing_errors() {
+        assert!("".parse::<Value>().is_err());
+        assert!("{".parse::<Value>().is_err());
+        assert!("[1,]".parse::<Value>().is_err());
+        assert!(r#"{"a" 1}"#.parse::<Value>().is_err());
+        assert!(r#""abc"#.parse::<Value>().is_err());
+        assert!("tru".parse::<Value>().is_err());
+        assert!("1 2".parse::<Value>().is_err());
+        assert!("--1".parse::<Value>().is_err());
+    }
+
+    #[test]
+    fn test_non_finite_floats() {
+        assert_eq!(Value::Float(f64::NAN).to_string(), "null");
+        assert_eq!(Value::Float(f64::INFINITY).to_string(), "null");
+    }
+}
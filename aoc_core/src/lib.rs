@@ -0,0 +1,402 @@
+use std::{any::Any, fmt, io::BufRead, str::FromStr};
+
+use anyhow::{anyhow, bail};
+
+pub mod alloc;
+pub mod cli;
+pub mod json;
+pub mod stream;
+pub mod trace;
+
+/// Answer to a single part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    Unimplemented,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(n) => write!(f, "{n}"),
+            Self::Text(s) => write!(f, "{s}"),
+            Self::Unimplemented => write!(f, "not implemented"),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Self::Integer(n.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        // usize is at most 64 bits wide on every supported target
+        Self::Integer(n as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Self::Integer(n as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::Text(s.to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::One => write!(f, "1"),
+            Self::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Self::One),
+            "2" => Ok(Self::Two),
+            s => bail!("couldn't parse part from {:?}, expected 1 or 2", s),
+        }
+    }
+}
+
+/// How an input is laid out, so tools taking it apart (e.g. to shrink a failing input) keep it
+/// recognizable as a puzzle input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Layout {
+    /// Independent lines, any of which can go.
+    #[default]
+    Lines,
+    /// Rectangular grid of cells, whose rows and columns can go, but not single cells.
+    Grid,
+    /// Sections separated by blank lines, all of which have to stay, along with the first
+    /// `header` lines of each.
+    Sections { header: usize },
+}
+
+/// Common interface implemented by every day crate, so tooling can parse and solve any day
+/// without knowing its concrete types.
+pub trait Solution {
+    type Problem: FromStr<Err = anyhow::Error>;
+
+    /// Day of the advent calendar, 1-based.
+    const DAY: u8;
+
+    /// Parts that have a solver; the others answer with [`Answer::Unimplemented`].
+    const IMPLEMENTED: &'static [Part] = &Part::ALL;
+
+    /// Example inputs from the puzzle description, for part 1 and part 2 respectively.
+    const EXAMPLES: [&'static str; 2];
+
+    /// Shape of the input, for tools taking it apart.
+    const LAYOUT: Layout = Layout::Lines;
+
+    /// # Errors
+    ///
+    /// Returns error when the solver fails on given problem.
+    fn part_1(_p: &Self::Problem) -> Result<Answer, anyhow::Error> {
+        Ok(Answer::Unimplemented)
+    }
+
+    /// # Errors
+    ///
+    /// Returns error when the solver fails on given problem.
+    fn part_2(_p: &Self::Problem) -> Result<Answer, anyhow::Error> {
+        Ok(Answer::Unimplemented)
+    }
+
+    /// Slow, but obviously correct solver of part 1, for checking [`Solution::part_1`] against.
+    ///
+    /// # Errors
+    ///
+    /// Returns error when the solver fails on given problem.
+    fn reference_part_1(_p: &Self::Problem) -> Result<Answer, anyhow::Error> {
+        Ok(Answer::Unimplemented)
+    }
+
+    /// Slow, but obviously correct solver of part 2, for checking [`Solution::part_2`] against.
+    ///
+    /// # Errors
+    ///
+    /// Returns error when the solver fails on given problem.
+    fn reference_part_2(_p: &Self::Problem) -> Result<Answer, anyhow::Error> {
+        Ok(Answer::Unimplemented)
+    }
+
+    /// Solves the part straight from a reader, a line at a time, so that inputs of any size fit
+    /// in memory (see [`stream::try_fold_lines`]). Only days whose lines are independent of each
+    /// other can do that, the rest give `None`.
+    fn stream(_input: &mut dyn BufRead, _part: Part) -> Option<Result<Answer, anyhow::Error>> {
+        None
+    }
+}
+
+/// Type-erased handle to a [`Solution`], so that days with different `Problem` types can be
+/// kept in a single registry. Parsing and solving are exposed separately, letting callers time
+/// each phase on its own.
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    pub day: u8,
+    pub implemented: &'static [Part],
+    pub examples: [&'static str; 2],
+    pub layout: Layout,
+    parse: fn(&str) -> Result<Box<dyn Any>, anyhow::Error>,
+    solve: fn(&dyn Any, Part) -> Result<Answer, anyhow::Error>,
+    reference: fn(&dyn Any, Part) -> Result<Answer, anyhow::Error>,
+    stream: fn(&mut dyn BufRead, Part) -> Option<Result<Answer, anyhow::Error>>,
+}
+
+fn parse_erased<S: Solution>(input: &str) -> Result<Box<dyn Any>, anyhow::Error>
+where
+    S::Problem: 'static,
+{
+    Ok(Box::new(input.parse::<S::Problem>()?))
+}
+
+fn solve_erased<S: Solution>(problem: &dyn Any, part: Part) -> Result<Answer, anyhow::Error>
+where
+    S::Problem: 'static,
+{
+    let p = problem
+        .downcast_ref::<S::Problem>()
+        .ok_or_else(|| anyhow!("problem of day {} has unexpected type", S::DAY))?;
+
+    match part {
+        Part::One => S::part_1(p),
+        Part::Two => S::part_2(p),
+    }
+}
+
+fn reference_erased<S: Solution>(problem: &dyn Any, part: Part) -> Result<Answer, anyhow::Error>
+where
+    S::Problem: 'static,
+{
+    let p = problem
+        .downcast_ref::<S::Problem>()
+        .ok_or_else(|| anyhow!("problem of day {} has unexpected type", S::DAY))?;
+
+    match part {
+        Part::One => S::reference_part_1(p),
+        Part::Two => S::reference_part_2(p),
+    }
+}
+
+impl Puzzle {
+    #[must_use]
+    pub const fn of<S: Solution>() -> Self
+    where
+        S::Problem: 'static,
+    {
+        Self {
+            day: S::DAY,
+            implemented: S::IMPLEMENTED,
+            examples: S::EXAMPLES,
+            layout: S::LAYOUT,
+            parse: parse_erased::<S>,
+            solve: solve_erased::<S>,
+            reference: reference_erased::<S>,
+            stream: S::stream,
+        }
+    }
+
+    #[must_use]
+    pub fn is_implemented(&self, part: Part) -> bool {
+        self.implemented.contains(&part)
+    }
+
+    #[must_use]
+    pub fn example(&self, part: Part) -> &'static str {
+        match part {
+            Part::One => self.examples[0],
+            Part::Two => self.examples[1],
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Returns error when input can't be parsed into the day's problem.
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Any>, anyhow::Error> {
+        (self.parse)(input)
+    }
+
+    /// # Errors
+    ///
+    /// Returns error when `problem` didn't come from [`Puzzle::parse`] of the same day, or when
+    /// the solver itself fails.
+    pub fn solve(&self, problem: &dyn Any, part: Part) -> Result<Answer, anyhow::Error> {
+        (self.solve)(problem, part)
+    }
+
+    /// Like [`Puzzle::solve`], but with the reference solver, which answers with
+    /// [`Answer::Unimplemented`] when there's none.
+    ///
+    /// # Errors
+    ///
+    /// Returns error when `problem` didn't come from [`Puzzle::parse`] of the same day, or when
+    /// the solver itself fails.
+    pub fn solve_reference(&self, problem: &dyn Any, part: Part) -> Result<Answer, anyhow::Error> {
+        (self.reference)(problem, part)
+    }
+
+    /// Like [`Puzzle::solve`], but reading the input as it goes, see [`Solution::stream`].
+    /// `None` when the day can't be solved that way.
+    pub fn solve_stream(
+        &self,
+        input: &mut dyn BufRead,
+        part: Part,
+    ) -> Option<Result<Answer, anyhow::Error>> {
+        (self.stream)(input, part)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_from() {
+        assert_eq!(Answer::from(42u32), Answer::Integer(42));
+        assert_eq!(Answer::from(-3i64), Answer::Integer(-3));
+        assert_eq!(Answer::from(7usize), Answer::Integer(7));
+        assert_eq!(Answer::from("abc"), Answer::Text("abc".to_string()));
+    }
+
+    #[test]
+    fn test_part_parsing() {
+        assert_eq!("1".parse::<Part>().unwrap(), Part::One);
+        assert_eq!("2".parse::<Part>().unwrap(), Part::Two);
+        assert!("3".parse::<Part>().is_err());
+    }
+
+    pub(crate) struct Number(i64);
+
+    impl FromStr for Number {
+        type Err = anyhow::Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Self(s.parse()?))
+        }
+    }
+
+    pub(crate) struct Doubling;
+
+    impl Solution for Doubling {
+        type Problem = Number;
+
+        const DAY: u8 = 1;
+        const IMPLEMENTED: &'static [Part] = &[Part::One];
+        const EXAMPLES: [&'static str; 2] = ["21", "21"];
+
+        fn part_1(p: &Number) -> Result<Answer, anyhow::Error> {
+            trace!("doubling {}", p.0);
+            Ok((p.0 * 2).into())
+        }
+
+        fn reference_part_1(p: &Number) -> Result<Answer, anyhow::Error> {
+            Ok((p.0 + p.0).into())
+        }
+
+        fn stream(input: &mut dyn BufRead, part: Part) -> Option<Result<Answer, anyhow::Error>> {
+            (part == Part::One).then(|| {
+                stream::try_fold_lines(
+                    input,
+                    0,
+                    |sum, line| Ok(sum + line.parse::<Number>()?.0 * 2),
+                )
+                .map(Answer::from)
+            })
+        }
+    }
+
+    #[test]
+    fn test_puzzle() {
+        let puzzle = Puzzle::of::<Doubling>();
+        assert_eq!(puzzle.day, 1);
+        assert_eq!(puzzle.layout, Layout::Lines);
+        assert!(puzzle.is_implemented(Part::One));
+        assert!(!puzzle.is_implemented(Part::Two));
+
+        let p = puzzle.parse("21").unwrap();
+        assert_eq!(
+            puzzle.solve(p.as_ref(), Part::One).unwrap(),
+            Answer::Integer(42)
+        );
+        assert_eq!(
+            puzzle.solve(p.as_ref(), Part::Two).unwrap(),
+            Answer::Unimplemented
+        );
+        assert_eq!(
+            puzzle.solve_reference(p.as_ref(), Part::One).unwrap(),
+            Answer::Integer(42)
+        );
+        assert_eq!(
+            puzzle.solve_reference(p.as_ref(), Part::Two).unwrap(),
+            Answer::Unimplemented
+        );
+        assert!(puzzle.parse("abc").is_err());
+        assert!(puzzle.solve(&"21", Part::One).is_err());
+
+        assert_eq!(
+            puzzle
+                .solve_stream(&mut "21\r\n".as_bytes(), Part::One)
+                .unwrap()
+                .unwrap(),
+            Answer::Integer(42)
+        );
+        assert!(puzzle
+            .solve_stream(&mut "21".as_bytes(), Part::Two)
+            .is_none());
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::Integer(-12).to_string(), "-12");
+        assert_eq!(Answer::Text("abc".to_string()).to_string(), "abc");
+        assert_eq!(Answer::Unimplemented.to_string(), "not implemented");
+    }
+}
@@ -0,0 +1,113 @@
+//! Solving inputs as they're read, a line at a time, for days whose lines don't depend on each
+//! other. Memory use then stays the same no matter how large the input gets.
+
+use std::io::BufRead;
+
+use anyhow::Context;
+use aoc_parse::ParseError;
+
+/// Folds every line of `input` into an answer, like [`Iterator::try_fold`]. Lines come
+/// normalized the same way [`aoc_parse::normalize`] does it for whole inputs, and a single
+/// buffer is reused for all of them.
+///
+/// # Errors
+///
+/// Returns error when the input can't be read or `f` fails. Errors get the number of the line
+/// that caused them; parse errors, which only know of a single line, have theirs fixed.
+pub fn try_fold_lines<B>(
+    input: &mut dyn BufRead,
+    init: B,
+    mut f: impl FnMut(B, &str) -> Result<B, anyhow::Error>,
+) -> Result<B, anyhow::Error> {
+    let mut acc = init;
+    let mut buffer = String::new();
+    let mut number = 0;
+    // blank lines only count once something follows them, trailing ones are dropped
+    let mut blank = 0;
+
+    let mut fold = |acc, number, line: &str| {
+        f(acc, line).map_err(|e| match e.downcast::<ParseError>() {
+            Ok(mut e) => {
+                e.line = number;
+                e.into()
+            }
+            Err(e) => e.context(format!("line {number}")),
+        })
+    };
+
+    loop {
+        buffer.clear();
+        if input
+            .read_line(&mut buffer)
+            .with_context(|| format!("couldn't read line {}", number + 1))?
+            == 0
+        {
+            return Ok(acc);
+        }
+        number += 1;
+
+        let line = if number == 1 {
+            buffer.strip_prefix('\u{feff}').unwrap_or(&buffer)
+        } else {
+            &buffer
+        };
+        let line = line.trim_end();
+
+        if line.is_empty() {
+            blank += 1;
+            continue;
+        }
+
+        for k in (1..=blank).rev() {
+            acc = fold(acc, number - k, "")?;
+        }
+        blank = 0;
+
+        acc = fold(acc, number, line)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: &str) -> Vec<String> {
+        try_fold_lines(&mut input.as_bytes(), vec![], |mut lines, line| {
+            lines.push(line.to_string());
+            Ok(lines)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_lines_are_normalized() {
+        assert_eq!(lines("\u{feff}a \r\n\r\nb\t\r\n\r\n\n"), ["a", "", "b"]);
+        assert_eq!(lines("a\nb"), ["a", "b"]);
+        assert_eq!(lines(""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_errors_point_at_lines() {
+        let sum = |input: &str| {
+            try_fold_lines(&mut input.as_bytes(), 0u8, |sum, line| {
+                let n = aoc_parse::Span::new(line).parse::<u8>()?;
+                sum.checked_add(n)
+                    .ok_or_else(|| anyhow::anyhow!("sum overflows"))
+            })
+        };
+
+        assert_eq!(sum("1\n2\n3\n\n").unwrap(), 6);
+        assert!(sum("1\n\nx")
+            .unwrap_err()
+            .to_string()
+            .starts_with("line 2, column 1: "));
+        assert!(sum("1\n2\nx")
+            .unwrap_err()
+            .to_string()
+            .starts_with("line 3, column 1: "));
+        assert_eq!(
+            format!("{:#}", sum("200\n100").unwrap_err()),
+            "line 2: sum overflows"
+        );
+    }
+}
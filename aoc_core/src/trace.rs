@@ -0,0 +1,89 @@
+//! Notes solvers leave on how they arrived at an answer, for finding out why it's wrong. They're
+//! left with [`trace!`](crate::trace!) and kept per thread, but only while something collects
+//! them (see [`collect`]); otherwise nothing even gets formatted.
+
+use std::cell::RefCell;
+
+thread_local! {
+    static NOTES: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Whether notes are being collected on this thread, for solvers to skip work only needed to
+/// describe what they're doing.
+#[must_use]
+pub fn enabled() -> bool {
+    NOTES.with_borrow(Option::is_some)
+}
+
+#[doc(hidden)]
+pub fn record(note: impl FnOnce() -> String) {
+    // the solver only leaves notes, keeping them isn't its own work
+    crate::alloc::uncounted(|| {
+        NOTES.with_borrow_mut(|notes| {
+            if let Some(notes) = notes {
+                notes.push(note());
+            }
+        });
+    });
+}
+
+// Puts back whatever was being collected before, even when `f` panics.
+struct Restore(Option<Vec<String>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        NOTES.set(self.0.take());
+    }
+}
+
+/// Runs `f`, collecting notes it leaves on the current thread. Notes of a nested `collect` stay
+/// with it.
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    let restore = Restore(NOTES.replace(Some(vec![])));
+    let result = f();
+    let notes = NOTES.take().unwrap_or_default();
+    drop(restore);
+
+    (result, notes)
+}
+
+/// Leaves a note, formatted like [`format!`], if notes are being collected.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::trace::enabled() {
+            $crate::trace::record(|| format!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use super::*;
+
+    #[test]
+    fn test_collect() {
+        trace!("nobody is listening");
+        assert!(!enabled());
+
+        let (n, notes) = collect(|| {
+            trace!("first {}", 1);
+            let ((), inner) = collect(|| trace!("inner"));
+            assert_eq!(inner, ["inner"]);
+            trace!("second");
+            2
+        });
+        assert_eq!(n, 2);
+        assert_eq!(notes, ["first 1", "second"]);
+        assert!(!enabled());
+    }
+
+    #[test]
+    fn test_collect_after_panic() {
+        let result = panic::catch_unwind(|| collect(|| panic!("boom")));
+        assert!(result.is_err());
+        assert!(!enabled());
+    }
+}
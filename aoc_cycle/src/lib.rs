@@ -0,0 +1,133 @@
+/// Shape of the sequence `x0, f(x0), f(f(x0)), ...` of a deterministic state machine: states
+/// from index `start` on repeat every `length` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Smallest number of steps leading to the same state as `n` steps do.
+    #[must_use]
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+fn advance<S>(mut state: S, step: &mut impl FnMut(&S) -> S, n: usize) -> S {
+    for _ in 0..n {
+        state = step(&state);
+    }
+
+    state
+}
+
+/// Finds the cycle every sequence over a finite set of states eventually runs into, using
+/// Brent's algorithm, which only keeps a couple of states around. Never returns when the states
+/// don't repeat.
+pub fn find_cycle<S: Clone + Eq>(initial: &S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // find the cycle length, moving the tortoise to the hare at every power of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // with the hare a cycle length ahead, both meet at the first repeated state
+    let mut start = 0;
+    let mut tortoise = initial.clone();
+    let mut hare = advance(initial.clone(), &mut step, length);
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// State after `n` steps, skipping whole repetitions of the cycle, so `n` can be far larger than
+/// the number of distinct states.
+pub fn state_after<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let cycle = find_cycle(&initial, &mut step);
+
+    advance(initial, &mut step, cycle.reduce(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 7 -> 5 -> ...
+    fn rho(&x: &u32) -> u32 {
+        if x < 7 {
+            x + 1
+        } else {
+            5
+        }
+    }
+
+    fn square(&x: &u32) -> u32 {
+        (x * x + 1) % 1009
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        assert_eq!(
+            find_cycle(&0, rho),
+            Cycle {
+                start: 5,
+                length: 3
+            }
+        );
+        assert_eq!(
+            find_cycle(&6, rho),
+            Cycle {
+                start: 0,
+                length: 3
+            }
+        );
+        assert_eq!(
+            find_cycle(&0, |&x| (x + 1) % 10),
+            Cycle {
+                start: 0,
+                length: 10
+            }
+        );
+        assert_eq!(
+            find_cycle(&42, |&x| x),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_state_after() {
+        for n in 0..50 {
+            assert_eq!(state_after(0, rho, n), advance(0, &mut rho, n));
+        }
+
+        assert_eq!(state_after(0, rho, 1_000_000_000), 7);
+        assert_eq!(
+            state_after(3, square, 100_000),
+            advance(3, &mut square, 100_000)
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{lines, Rng};
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn calibration_line(rng: &mut Rng) -> String {
+    let mut line = String::new();
+
+    for _ in 0..rng.range(1..=8) {
+        match rng.range(0..=2) {
+            0 => line.push(char::from(b'0' + rng.range(1..=9))),
+            1 => line += *rng.choose(&WORDS),
+            _ => line.extend((0..rng.range(1..=5)).map(|_| char::from(b'a' + rng.range(0..=25)))),
+        }
+    }
+
+    // every line has at least one digit, words alone wouldn't do for part 1
+    if !line.chars().any(|c| c.is_ascii_digit()) {
+        let at = rng.range(0..=line.len());
+        line.insert(at, char::from(b'0' + rng.range(1..=9)));
+    }
+
+    line
+}
+
+/// `size` calibration lines, mixing letters with digits and spelled out digits.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| calibration_line(rng)))
+}
@@ -0,0 +1,25 @@
+use crate::{lines, Rng};
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+fn subset(rng: &mut Rng) -> String {
+    let mut colors = COLORS;
+    rng.shuffle(&mut colors);
+
+    colors[..rng.range(1..=3)]
+        .iter()
+        .map(|color| format!("{} {}", rng.range(1..=20), color))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `size` games of one to six rounds, each revealing up to twenty cubes of some colors.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    lines((1..=size).map(|id| {
+        let subsets = (0..rng.range(1..=6))
+            .map(|_| subset(rng))
+            .collect::<Vec<_>>();
+
+        format!("Game {}: {}", id, subsets.join("; "))
+    }))
+}
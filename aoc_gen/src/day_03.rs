@@ -0,0 +1,38 @@
+use crate::{lines, Rng};
+
+// gears show up more often than other symbols
+const SYMBOLS: &[u8] = b"**#+$/@=%&-";
+
+fn symbol(rng: &mut Rng) -> char {
+    char::from(*rng.choose(SYMBOLS))
+}
+
+fn schematic_row(rng: &mut Rng, width: usize) -> String {
+    let mut row = String::with_capacity(width);
+
+    while row.len() < width {
+        if rng.chance(1, 5) {
+            let digits = rng.range(1..=3).min(width - row.len());
+
+            row.push(char::from(b'0' + rng.range(1..=9)));
+            row.extend((1..digits).map(|_| char::from(b'0' + rng.range(0..=9))));
+
+            // numbers on the same row never touch
+            if row.len() < width {
+                row.push(if rng.chance(1, 4) { symbol(rng) } else { '.' });
+            }
+        } else if rng.chance(1, 10) {
+            row.push(symbol(rng));
+        } else {
+            row.push('.');
+        }
+    }
+
+    row
+}
+
+/// Engine schematic of `size` by `size` cells, numbers of up to three digits scattered among
+/// symbols.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| schematic_row(rng, size)))
+}
@@ -0,0 +1,45 @@
+use crate::{lines, Rng};
+
+const WINNING: usize = 10;
+const NUMBERS: usize = 25;
+
+// Most cards win nothing. On average a card wins less than one copy, otherwise the number of
+// copies would grow exponentially with the number of cards.
+fn matching(rng: &mut Rng) -> usize {
+    match rng.range(0..=19) {
+        0..=13 => 0,
+        14..=18 => rng.range(1..=2),
+        _ => rng.range(3..=WINNING),
+    }
+}
+
+fn numbers(numbers: &[u8]) -> String {
+    numbers
+        .iter()
+        .map(|n| format!("{n:>2}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `size` scratchcards of ten winning numbers and twenty-five numbers one has, none winning
+/// copies of cards past the end of the table.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.to_string().len();
+
+    lines((1..=size).map(|id| {
+        let mut pool = (1..=99).collect::<Vec<u8>>();
+        rng.shuffle(&mut pool);
+
+        let matching = matching(rng).min(size - id);
+        let winning = &pool[..WINNING];
+        let mut have = [&winning[..matching], &pool[WINNING..][..NUMBERS - matching]].concat();
+        rng.shuffle(&mut have);
+
+        format!(
+            "Card {:>width$}: {} | {}",
+            id,
+            numbers(winning),
+            numbers(&have)
+        )
+    }))
+}
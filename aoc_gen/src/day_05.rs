@@ -0,0 +1,68 @@
+use crate::{lines, Rng};
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+// numbers of the real almanacs fit in 32 bits
+const LIMIT: u64 = 1 << 32;
+
+// Source ranges of a map never overlap, so they're cut out of `0..LIMIT`, with some of the
+// pieces left unmapped.
+fn map_lines(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut cuts = (0..rng.range(0..=size))
+        .map(|_| rng.range(1..=LIMIT - 1))
+        .chain([0, LIMIT])
+        .collect::<Vec<_>>();
+    cuts.sort_unstable();
+    cuts.dedup();
+
+    let mut ranges = cuts
+        .windows(2)
+        .filter_map(|w| {
+            let (src, length) = (w[0], w[1] - w[0]);
+            let dst = rng.range(0..=LIMIT - length);
+
+            rng.chance(4, 5).then(|| format!("{dst} {src} {length}"))
+        })
+        .collect::<Vec<_>>();
+
+    if ranges.is_empty() {
+        ranges.push(format!("{} 0 {}", rng.range(0..=LIMIT / 2), LIMIT / 2));
+    }
+
+    rng.shuffle(&mut ranges);
+    ranges
+}
+
+/// Almanac of `size` seed ranges, and maps of up to `size` ranges each.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    let seeds = (0..size)
+        .map(|_| {
+            let length = rng.range(1..=LIMIT / 16);
+
+            format!("{} {}", rng.range(0..=LIMIT - length), length)
+        })
+        .collect::<Vec<_>>();
+
+    let maps = CATEGORIES.windows(2).map(|w| {
+        let mut map = vec![format!("{}-to-{} map:", w[0], w[1])];
+        map.extend(map_lines(rng, size));
+
+        lines(map)
+    });
+
+    let sections = [format!("seeds: {}", seeds.join(" "))]
+        .into_iter()
+        .chain(maps)
+        .collect::<Vec<_>>();
+
+    sections.join("\n\n")
+}
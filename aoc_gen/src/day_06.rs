@@ -0,0 +1,22 @@
+use crate::{lines, Rng};
+
+/// Records of `size` races, but at most four of them, so that numbers of the single long race of
+/// part 2 still fit in `u64`. Every record can be beaten.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    let races = (0..size.min(4))
+        .map(|_| {
+            let time = rng.range(7..=99u64);
+            let best = (time / 2) * (time - time / 2);
+
+            (time, rng.range(0..=best - 1))
+        })
+        .collect::<Vec<_>>();
+
+    let times = races.iter().map(|(time, _)| format!("{time:>5}"));
+    let distances = races.iter().map(|(_, distance)| format!("{distance:>5}"));
+
+    lines([
+        format!("{:<9}{}", "Time:", times.collect::<String>()),
+        format!("{:<9}{}", "Distance:", distances.collect::<String>()),
+    ])
+}
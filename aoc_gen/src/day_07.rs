@@ -0,0 +1,14 @@
+use crate::{lines, Rng};
+
+const CARDS: &[u8] = b"AKQJT98765432";
+
+/// `size` hands of five cards, each with a bid of up to a thousand.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let hand = (0..5)
+            .map(|_| char::from(*rng.choose(CARDS)))
+            .collect::<String>();
+
+        format!("{} {}", hand, rng.range(1..=1000))
+    }))
+}
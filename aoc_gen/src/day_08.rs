@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+
+use crate::{lines, Rng};
+
+// Each ghost needs a prime multiple of the instructions' length to get to its end node.
+const PRIMES: [usize; 6] = [2, 3, 5, 7, 11, 13];
+
+const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Hands out unique node names, of which only start and end nodes end with `A` and `Z`.
+struct Names {
+    taken: HashSet<String>,
+    length: usize,
+}
+
+impl Names {
+    fn new(nodes: usize) -> Self {
+        let mut length = 3;
+
+        // keep names sparse enough for random ones to be mostly fresh
+        while 26usize.pow(length as u32 - 1) * 24 < nodes * 2 {
+            length += 1;
+        }
+
+        Self {
+            taken: HashSet::from(["AAA".to_string(), "ZZZ".to_string()]),
+            length,
+        }
+    }
+
+    fn fresh(&mut self, rng: &mut Rng, last: Option<u8>) -> String {
+        loop {
+            let mut name = (1..self.length)
+                .map(|_| char::from(*rng.choose(LETTERS)))
+                .collect::<String>();
+            name.push(char::from(
+                last.unwrap_or_else(|| *rng.choose(&LETTERS[1..(LETTERS.len() - 1)])),
+            ));
+
+            if self.taken.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+}
+
+/// `size` instructions and a network walked by up to six ghosts, the first one from `AAA` to
+/// `ZZZ`. Like in the real puzzle, each ghost follows its own loop, coming back to where it
+/// started from after reaching its end node.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    let instructions = (0..size)
+        .map(|_| if rng.chance(1, 2) { 'L' } else { 'R' })
+        .collect::<Vec<_>>();
+
+    let mut primes = PRIMES;
+    rng.shuffle(&mut primes);
+    let primes = &primes[..rng.range(1..=PRIMES.len())];
+
+    let mut names = Names::new(size * primes.iter().sum::<usize>());
+    let mut network = vec![];
+
+    for (ghost, &prime) in primes.iter().enumerate() {
+        let steps = size * prime;
+
+        let mut path = Vec::with_capacity(steps + 1);
+        path.push(if ghost == 0 {
+            "AAA".to_string()
+        } else {
+            names.fresh(rng, Some(b'A'))
+        });
+        path.extend((1..steps).map(|_| names.fresh(rng, None)));
+        path.push(if ghost == 0 {
+            "ZZZ".to_string()
+        } else {
+            names.fresh(rng, Some(b'Z'))
+        });
+
+        // The instruction taken at a node always leads further along the path, the other one
+        // anywhere else on it. The end node leads wherever the start node does.
+        let mut choices = Vec::with_capacity(steps);
+
+        for i in 0..steps {
+            let next = &path[i + 1];
+            let other = &path[rng.range(0..=steps)];
+
+            choices.push(match instructions[i % size] {
+                'L' => (next.clone(), other.clone()),
+                _ => (other.clone(), next.clone()),
+            });
+        }
+
+        choices.push(choices[0].clone());
+
+        network.extend(
+            path.iter()
+                .zip(choices)
+                .map(|(node, (left, right))| format!("{node} = ({left}, {right})")),
+        );
+    }
+
+    rng.shuffle(&mut network);
+
+    format!(
+        "{}\n\n{}",
+        instructions.into_iter().collect::<String>(),
+        lines(network)
+    )
+}
@@ -0,0 +1,30 @@
+use crate::{lines, Rng};
+
+const LENGTH: usize = 21;
+
+// Values of a polynomial of a degree low enough for its differences to reach zero well within
+// the sequence. Its first value and the first values of each row of differences are random, the
+// rest follows from them.
+fn sequence(rng: &mut Rng) -> String {
+    let degree = rng.range(0..=8);
+    let mut column = (0..=degree)
+        .map(|_| rng.range(-9i64..=9))
+        .collect::<Vec<_>>();
+
+    let mut values = Vec::with_capacity(LENGTH);
+
+    for _ in 0..LENGTH {
+        values.push(column[0].to_string());
+
+        for i in 0..degree {
+            column[i] += column[i + 1];
+        }
+    }
+
+    values.join(" ")
+}
+
+/// `size` sequences of twenty-one values of a polynomial.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| sequence(rng)))
+}
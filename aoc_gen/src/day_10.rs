@@ -0,0 +1,196 @@
+use crate::{lines, Rng};
+
+const NORTH: u8 = 1;
+const EAST: u8 = 2;
+const SOUTH: u8 = 4;
+const WEST: u8 = 8;
+
+const JUNK: &[u8] = b"|-LJ7F..";
+
+const PIPES: [(u8, u8); 6] = [
+    (b'|', NORTH | SOUTH),
+    (b'-', EAST | WEST),
+    (b'L', NORTH | EAST),
+    (b'J', NORTH | WEST),
+    (b'7', SOUTH | WEST),
+    (b'F', SOUTH | EAST),
+];
+
+fn pipe(connections: u8) -> Option<u8> {
+    PIPES
+        .iter()
+        .find(|&&(_, c)| c == connections)
+        .map(|&(pipe, _)| pipe)
+}
+
+fn connects(tile: u8, direction: u8) -> bool {
+    PIPES
+        .iter()
+        .any(|&(pipe, c)| pipe == tile && c & direction != 0)
+}
+
+/// Which neighbours a block of the tree is joined with, besides those joined with it.
+#[derive(Clone, Copy, Default)]
+struct Joins {
+    east: bool,
+    south: bool,
+}
+
+// Grows a random tree over `blocks` by `blocks` cells, those left out of it being `None`.
+fn block_tree(rng: &mut Rng, blocks: usize) -> Vec<Vec<Option<Joins>>> {
+    let mut tree = vec![vec![None; blocks]; blocks];
+
+    let root = (rng.range(0..=blocks - 1), rng.range(0..=blocks - 1));
+    tree[root.0][root.1] = Some(Joins::default());
+    let mut cells = vec![root];
+
+    let target = (blocks * blocks * 3 / 5).max(1);
+
+    for _ in 0..(target * 20) {
+        if cells.len() == target {
+            break;
+        }
+
+        let (i, j) = *rng.choose(&cells);
+
+        let neighbour = match rng.range(0..=3) {
+            0 if i > 0 => (i - 1, j),
+            1 if j + 1 < blocks => (i, j + 1),
+            2 if i + 1 < blocks => (i + 1, j),
+            3 if j > 0 => (i, j - 1),
+            _ => continue,
+        };
+
+        if tree[neighbour.0][neighbour.1].is_some() {
+            continue;
+        }
+
+        tree[neighbour.0][neighbour.1] = Some(Joins::default());
+        cells.push(neighbour);
+
+        let (a, b) = ((i, j).min(neighbour), (i, j).max(neighbour));
+
+        if let Some(joins) = &mut tree[a.0][a.1] {
+            if a.0 == b.0 {
+                joins.east = true;
+            } else {
+                joins.south = true;
+            }
+        }
+    }
+
+    tree
+}
+
+// Side of a block of tiles the loop goes around.
+const BLOCK: usize = 3;
+
+/// Field of `size` by `size` tiles (at least three) with a single loop of pipes through the
+/// start tile and unconnected pipes all around it.
+///
+/// The loop goes around a random tree of 3 by 3 blocks of tiles, hugging its edges: each block
+/// is bordered by a small loop of its own, opened up towards the blocks it's joined with.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(BLOCK);
+    let blocks = size / BLOCK;
+    let tree = block_tree(rng, blocks);
+
+    let mut connections = vec![vec![0u8; size]; size];
+
+    // `b` is either east or south of `a`
+    let mut connect = |a: (usize, usize), b: (usize, usize)| {
+        let (from, to) = if a.0 == b.0 {
+            (EAST, WEST)
+        } else {
+            (SOUTH, NORTH)
+        };
+
+        connections[a.0][a.1] |= from;
+        connections[b.0][b.1] |= to;
+    };
+
+    for i in 0..blocks {
+        for j in 0..blocks {
+            let Some(Joins { east, south }) = tree[i][j] else {
+                continue;
+            };
+
+            let (top, left) = (BLOCK * i, BLOCK * j);
+            let (bottom, right) = (top + BLOCK - 1, left + BLOCK - 1);
+            let north = i > 0 && tree[i - 1][j].is_some_and(|joins| joins.south);
+            let west = j > 0 && tree[i][j - 1].is_some_and(|joins| joins.east);
+
+            // Joining two loops takes a pair of parallel edges out of them, one from each, and
+            // crosses over between their ends instead.
+            for c in left..right {
+                if c != left || !north {
+                    connect((top, c), (top, c + 1));
+                }
+
+                if c != left || !south {
+                    connect((bottom, c), (bottom, c + 1));
+                }
+            }
+
+            for r in top..bottom {
+                if r != top || !west {
+                    connect((r, left), (r + 1, left));
+                }
+
+                if r != top || !east {
+                    connect((r, right), (r + 1, right));
+                }
+            }
+
+            if east {
+                connect((top, right), (top, right + 1));
+                connect((top + 1, right), (top + 1, right + 1));
+            }
+
+            if south {
+                connect((bottom, left), (bottom + 1, left));
+                connect((bottom, left + 1), (bottom + 1, left + 1));
+            }
+        }
+    }
+
+    let loop_tiles = (0..size)
+        .flat_map(|r| (0..size).map(move |c| (r, c)))
+        .filter(|&(r, c)| connections[r][c] != 0)
+        .collect::<Vec<_>>();
+    let start = *rng.choose(&loop_tiles);
+
+    let mut field = connections
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&c| pipe(c).unwrap_or_else(|| *rng.choose(JUNK)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    field[start.0][start.1] = b'S';
+
+    // junk next to the start tile mustn't look like it leads into it
+    let (r, c) = start;
+    let neighbours = [
+        (r.checked_sub(1).map(|r| (r, c)), SOUTH),
+        ((c + 1 < size).then_some((r, c + 1)), WEST),
+        ((r + 1 < size).then_some((r + 1, c)), NORTH),
+        (c.checked_sub(1).map(|c| (r, c)), EAST),
+    ];
+
+    for (neighbour, towards_start) in neighbours {
+        if let Some((nr, nc)) = neighbour {
+            if connections[nr][nc] == 0 && connects(field[nr][nc], towards_start) {
+                field[nr][nc] = b'.';
+            }
+        }
+    }
+
+    lines(
+        field
+            .into_iter()
+            .map(|row| row.into_iter().map(char::from).collect()),
+    )
+}
@@ -0,0 +1,22 @@
+use crate::{lines, Rng};
+
+/// Image of `size` by `size` pixels with galaxies scattered across it, leaving some rows and
+/// columns empty.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    let empty_columns = (0..size).map(|_| rng.chance(1, 8)).collect::<Vec<_>>();
+
+    lines((0..size).map(|_| {
+        let empty_row = rng.chance(1, 8);
+
+        empty_columns
+            .iter()
+            .map(|&empty_column| {
+                if !empty_row && !empty_column && rng.chance(1, 20) {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect()
+    }))
+}
@@ -0,0 +1,44 @@
+use crate::{lines, Rng};
+
+// Part 1 tries every arrangement of unknown springs, so there mustn't be too many of them.
+const MAX_UNKNOWN: usize = 14;
+
+fn condition_record(rng: &mut Rng) -> String {
+    let groups = (0..rng.range(1..=6))
+        .map(|_| rng.range(1..=5usize))
+        .collect::<Vec<_>>();
+
+    let mut springs = ".".repeat(rng.range(0..=2));
+
+    for (i, &group) in groups.iter().enumerate() {
+        if i > 0 {
+            springs += &".".repeat(rng.range(1..=3));
+        }
+
+        springs += &"#".repeat(group);
+    }
+
+    springs += &".".repeat(rng.range(0..=2));
+
+    let mut unknown = 0;
+    let record = springs
+        .chars()
+        .map(|c| {
+            if unknown < MAX_UNKNOWN && rng.chance(1, 2) {
+                unknown += 1;
+                '?'
+            } else {
+                c
+            }
+        })
+        .collect::<String>();
+
+    let criteria = groups.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    format!("{} {}", record, criteria.join(","))
+}
+
+/// `size` condition records, each having at least one arrangement.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| condition_record(rng)))
+}
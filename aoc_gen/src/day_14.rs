@@ -0,0 +1,15 @@
+use crate::{lines, Rng};
+
+/// Platform of `size` by `size` cells, with rounded rocks and cube-shaped rocks scattered over
+/// it.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        (0..size)
+            .map(|_| match rng.range(0..=19) {
+                0..=3 => 'O',
+                4..=6 => '#',
+                _ => '.',
+            })
+            .collect()
+    }))
+}
@@ -0,0 +1,74 @@
+use std::collections::HashSet;
+
+use crate::{lines, Rng};
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+fn names(rng: &mut Rng, count: usize) -> Vec<String> {
+    let mut taken = HashSet::new();
+    let mut names = vec!["in".to_string()];
+    let mut length = 3;
+
+    // keep names sparse enough for random ones to be mostly fresh
+    while 26usize.pow(length) < count * 2 {
+        length += 1;
+    }
+
+    while names.len() < count {
+        let name = (0..rng.range(2..=length))
+            .map(|_| char::from(*rng.choose(LETTERS)))
+            .collect::<String>();
+
+        if name != "in" && taken.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+
+    names
+}
+
+fn condition(rng: &mut Rng) -> String {
+    format!(
+        "{}{}{}",
+        char::from(*rng.choose(b"xmas")),
+        char::from(*rng.choose(b"<>")),
+        rng.range(1..=4000)
+    )
+}
+
+/// `size` workflows and `size` parts to sort. Workflows form a tree rooted at `in`, so parts
+/// never go around in circles.
+pub(crate) fn generate(rng: &mut Rng, size: usize) -> String {
+    let names = names(rng, size);
+
+    // every workflow but `in` is sent to from exactly one workflow before it
+    let mut children = vec![vec![]; size];
+
+    for i in 1..size {
+        children[rng.range(0..=i - 1)].push(names[i].clone());
+    }
+
+    let workflows = lines(names.iter().zip(children).map(|(name, mut targets)| {
+        while targets.len() < 2 || rng.chance(1, 3) {
+            targets.push((if rng.chance(1, 2) { "A" } else { "R" }).to_string());
+        }
+
+        rng.shuffle(&mut targets);
+        let fallback = targets.pop().unwrap_or_default();
+
+        let rules = targets
+            .into_iter()
+            .map(|target| format!("{}:{}", condition(rng), target))
+            .collect::<Vec<_>>();
+
+        format!("{}{{{},{}}}", name, rules.join(","), fallback)
+    }));
+
+    let parts = lines((0..size).map(|_| {
+        let [x, m, a, s] = [(); 4].map(|()| rng.range(1..=4000));
+
+        format!("{{x={x},m={m},a={a},s={s}}}")
+    }));
+
+    format!("{workflows}\n\n{parts}")
+}
@@ -0,0 +1,99 @@
+use anyhow::anyhow;
+
+mod day_01;
+mod day_02;
+mod day_03;
+mod day_04;
+mod day_05;
+mod day_06;
+mod day_07;
+mod day_08;
+mod day_09;
+mod day_10;
+mod day_11;
+mod day_12;
+mod day_14;
+mod day_19;
+mod mutate;
+mod rng;
+
+pub use mutate::mutate;
+pub use rng::{Rng, Sample};
+
+/// Produces random puzzle inputs for a single day. They're well-formed and solvable, shaped
+/// like the real ones, but can be made arbitrarily large to stress the solvers.
+#[derive(Clone, Copy)]
+pub struct Generator {
+    pub day: u8,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    const fn new(day: u8, generate: fn(&mut Rng, usize) -> String) -> Self {
+        Self { day, generate }
+    }
+
+    /// Input determined by `seed` alone. What `size` counts depends on the day, e.g. lines of
+    /// the input or the side of a grid; sizes below 1 are treated as 1.
+    #[must_use]
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        (self.generate)(&mut Rng::new(seed), size.max(1))
+    }
+}
+
+/// Generator of every day with a solution, in calendar order.
+pub const GENERATORS: &[Generator] = &[
+    Generator::new(1, day_01::generate),
+    Generator::new(2, day_02::generate),
+    Generator::new(3, day_03::generate),
+    Generator::new(4, day_04::generate),
+    Generator::new(5, day_05::generate),
+    Generator::new(6, day_06::generate),
+    Generator::new(7, day_07::generate),
+    Generator::new(8, day_08::generate),
+    Generator::new(9, day_09::generate),
+    Generator::new(10, day_10::generate),
+    Generator::new(11, day_11::generate),
+    Generator::new(12, day_12::generate),
+    Generator::new(14, day_14::generate),
+    Generator::new(19, day_19::generate),
+];
+
+/// # Errors
+///
+/// Returns error when the day has no generator.
+pub fn find(day: u8) -> Result<&'static Generator, anyhow::Error> {
+    GENERATORS
+        .iter()
+        .find(|g| g.day == day)
+        .ok_or_else(|| anyhow!("day {} has no input generator yet", day))
+}
+
+fn lines(lines: impl IntoIterator<Item = String>) -> String {
+    lines.into_iter().collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generators_are_sorted_and_unique() {
+        assert!(GENERATORS.windows(2).all(|w| w[0].day < w[1].day));
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(5).unwrap().day, 5);
+        assert!(find(26).is_err());
+    }
+
+    #[test]
+    fn test_inputs_depend_on_seed() {
+        for generator in GENERATORS {
+            assert_eq!(generator.generate(7, 20), generator.generate(7, 20));
+            assert_ne!(generator.generate(7, 20), generator.generate(8, 20));
+            assert_eq!(generator.generate(7, 0), generator.generate(7, 1));
+        }
+    }
+}
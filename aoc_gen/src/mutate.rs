@@ -0,0 +1,217 @@
+use crate::Rng;
+
+// Characters likely to trip up parsers: separators, signs, digits, whitespace of all sorts and
+// characters taking more than a single byte.
+const CHARACTERS: &[char] = &[
+    ' ',
+    '\n',
+    '\t',
+    '\r',
+    ':',
+    ',',
+    ';',
+    '|',
+    '=',
+    '-',
+    '+',
+    '0',
+    '1',
+    '9',
+    '.',
+    '#',
+    'x',
+    'A',
+    '\u{0}',
+    '\u{e9}',
+    '\u{3000}',
+    '\u{1f384}',
+];
+
+// Numbers around the limits of common integer types.
+const NUMBERS: &[&str] = &[
+    "0",
+    "-0",
+    "-1",
+    "255",
+    "256",
+    "4294967296",
+    "9223372036854775808",
+    "18446744073709551616",
+    "340282366920938463463374607431768211456",
+    "99999999999999999999999999999999999999999999",
+];
+
+/// Single edit made to an input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mutation {
+    DropChar,
+    InsertChar,
+    ReplaceChar,
+    DropLine,
+    DuplicateLine,
+    SwapLines,
+    DropWord,
+    DuplicateWord,
+    HugeNumber,
+    Truncate,
+}
+
+const MUTATIONS: [Mutation; 10] = [
+    Mutation::DropChar,
+    Mutation::InsertChar,
+    Mutation::ReplaceChar,
+    Mutation::DropLine,
+    Mutation::DuplicateLine,
+    Mutation::SwapLines,
+    Mutation::DropWord,
+    Mutation::DuplicateWord,
+    Mutation::HugeNumber,
+    Mutation::Truncate,
+];
+
+// Byte ranges of maximal runs of characters matching `predicate`.
+fn runs(s: &str, predicate: impl Fn(char) -> bool) -> Vec<(usize, usize)> {
+    let mut runs = vec![];
+    let mut start = None;
+
+    for (i, c) in s.char_indices().chain([(s.len(), '\n')]) {
+        match (start, predicate(c) && i < s.len()) {
+            (None, true) => start = Some(i),
+            (Some(j), false) => {
+                runs.push((j, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    runs
+}
+
+fn apply(rng: &mut Rng, mutation: Mutation, input: &str) -> String {
+    let mut chars = input.chars().collect::<Vec<_>>();
+    let mut lines = input.split('\n').collect::<Vec<_>>();
+
+    match mutation {
+        Mutation::DropChar if !chars.is_empty() => {
+            chars.remove(rng.range(0..=chars.len() - 1));
+        }
+        Mutation::InsertChar => {
+            let at = rng.range(0..=chars.len());
+            chars.insert(at, *rng.choose(CHARACTERS));
+        }
+        Mutation::ReplaceChar if !chars.is_empty() => {
+            let at = rng.range(0..=chars.len() - 1);
+            chars[at] = *rng.choose(CHARACTERS);
+        }
+        Mutation::DropLine => {
+            lines.remove(rng.range(0..=lines.len() - 1));
+            return lines.join("\n");
+        }
+        Mutation::DuplicateLine => {
+            let at = rng.range(0..=lines.len() - 1);
+            lines.insert(at, lines[at]);
+            return lines.join("\n");
+        }
+        Mutation::SwapLines => {
+            let (a, b) = (
+                rng.range(0..=lines.len() - 1),
+                rng.range(0..=lines.len() - 1),
+            );
+            lines.swap(a, b);
+            return lines.join("\n");
+        }
+        Mutation::DropWord | Mutation::DuplicateWord | Mutation::HugeNumber => {
+            let runs = if mutation == Mutation::HugeNumber {
+                runs(input, |c| c.is_ascii_digit())
+            } else {
+                runs(input, |c| !c.is_whitespace())
+            };
+
+            if !runs.is_empty() {
+                let (start, end) = *rng.choose(&runs);
+
+                return match mutation {
+                    Mutation::DropWord => format!("{}{}", &input[..start], &input[end..]),
+                    Mutation::DuplicateWord => format!("{} {}", &input[..end], &input[start..]),
+                    _ => format!(
+                        "{}{}{}",
+                        &input[..start],
+                        rng.choose(NUMBERS),
+                        &input[end..]
+                    ),
+                };
+            }
+        }
+        Mutation::Truncate => {
+            chars.truncate(rng.range(0..=chars.len()));
+        }
+        _ => {}
+    }
+
+    chars.into_iter().collect()
+}
+
+/// Input with one to four random edits, from dropping, inserting or replacing single characters,
+/// through dropping, duplicating or swapping words and lines, to blowing up numbers.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut input = input.to_string();
+
+    for _ in 0..rng.range(1..=4) {
+        let mutation = *rng.choose(&MUTATIONS);
+        input = apply(rng, mutation, &input);
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runs() {
+        assert_eq!(
+            runs("ab 12 é3", |c| !c.is_whitespace()),
+            vec![(0, 2), (3, 5), (6, 9)]
+        );
+        assert_eq!(
+            runs("ab 12 é3", |c| c.is_ascii_digit()),
+            vec![(3, 5), (8, 9)]
+        );
+        assert_eq!(runs("", |_| true), vec![]);
+    }
+
+    #[test]
+    fn test_mutations() {
+        let input = "seeds: 79 14\nx = 1";
+        let mut rng = Rng::new(5);
+
+        for _ in 0..20 {
+            assert!(apply(&mut rng, Mutation::DropChar, input).len() < input.len());
+            assert!(apply(&mut rng, Mutation::DropWord, input).len() < input.len());
+            assert!(apply(&mut rng, Mutation::DuplicateLine, input).len() > input.len());
+            assert!(apply(&mut rng, Mutation::DuplicateWord, input).len() > input.len());
+
+            let mutated = apply(&mut rng, Mutation::HugeNumber, input);
+            assert!(NUMBERS.iter().any(|n| mutated.contains(&format!(" {n}"))));
+
+            let mutated = apply(&mut rng, Mutation::SwapLines, input);
+            assert_eq!(mutated.lines().count(), 2);
+        }
+
+        assert_eq!(apply(&mut rng, Mutation::DropChar, ""), "");
+        assert_eq!(apply(&mut rng, Mutation::HugeNumber, "abc"), "abc");
+    }
+
+    #[test]
+    fn test_mutate() {
+        let input = "0 3 6 9 12 15\n1 3 6 10 15 21";
+        let mutated = (0..10)
+            .map(|seed| mutate(&mut Rng::new(seed), input))
+            .collect::<Vec<_>>();
+
+        assert!(mutated.iter().any(|m| m != input));
+        assert_eq!(mutated[3], mutate(&mut Rng::new(3), input));
+    }
+}
@@ -0,0 +1,176 @@
+use std::ops::RangeInclusive;
+
+/// Seeded pseudo-random number generator, SplitMix64 from <https://prng.di.unimi.it/>. Far from
+/// cryptographically secure, but fast, tiny and plenty random for shaping puzzle inputs, which
+/// only need to be reproducible from their seed.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+        z ^ (z >> 31)
+    }
+
+    /// Uniformly distributed number in `0..n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `n` is zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "no number is below zero");
+
+        // Lemire's multiply and reject, which avoids the bias of taking a remainder
+        let threshold = n.wrapping_neg() % n;
+
+        loop {
+            let m = u128::from(self.next_u64()) * u128::from(n);
+
+            if m as u64 >= threshold {
+                return (m >> 64) as u64;
+            }
+        }
+    }
+
+    /// Uniformly distributed number in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `range` is empty.
+    pub fn range<T: Sample>(&mut self, range: RangeInclusive<T>) -> T {
+        let (start, end) = (range.start().to_i128(), range.end().to_i128());
+        assert!(start <= end, "can't pick a number from an empty range");
+
+        let offset = match u64::try_from(end - start + 1) {
+            Ok(span) => self.below(span),
+            // the range covers every 64 bit number
+            Err(_) => self.next_u64(),
+        };
+
+        T::from_i128(start + i128::from(offset))
+    }
+
+    /// `true` with probability of `numerator / denominator`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `denominator` is zero.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    /// # Panics
+    ///
+    /// Panics when `items` are empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        assert!(!items.is_empty(), "can't choose from no items");
+
+        &items[self.range(0..=items.len() - 1)]
+    }
+
+    /// Fisher-Yates shuffle.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i));
+        }
+    }
+}
+
+/// Integers [`Rng::range`] can pick from. Every one of them fits in `i128`, which makes the
+/// arithmetic on range bounds trivial.
+pub trait Sample: Copy {
+    fn to_i128(self) -> i128;
+
+    fn from_i128(n: i128) -> Self;
+}
+
+macro_rules! impl_sample {
+    ($($t:ty),*) => {
+        $(
+            impl Sample for $t {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(n: i128) -> Self {
+                    n as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_sample!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_output() {
+        // first outputs of the reference implementation seeded with 0
+        let mut rng = Rng::new(0);
+        assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+        assert_eq!(rng.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+    }
+
+    #[test]
+    fn test_seeds() {
+        let sample = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..10).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(sample(7), sample(7));
+        assert_ne!(sample(7), sample(8));
+    }
+
+    #[test]
+    fn test_range() {
+        let mut rng = Rng::new(42);
+
+        let rolls = (0..1000).map(|_| rng.range(1..=6)).collect::<Vec<u8>>();
+        assert!(rolls.iter().all(|r| (1..=6).contains(r)));
+        assert!((1..=6).all(|face| rolls.contains(&face)));
+
+        assert!((0..100).all(|_| (-3..=-1).contains(&rng.range(-3i64..=-1))));
+        assert_eq!(rng.range(5usize..=5), 5);
+
+        // whole ranges of the widest types mustn't overflow
+        rng.range(i64::MIN..=i64::MAX);
+        rng.range(0..=u64::MAX);
+    }
+
+    #[test]
+    fn test_chance() {
+        let mut rng = Rng::new(1);
+        assert!((0..100).all(|_| rng.chance(1, 1)));
+        assert!((0..100).all(|_| !rng.chance(0, 3)));
+
+        let hits = (0..10_000).filter(|_| rng.chance(1, 4)).count();
+        assert!((2000..3000).contains(&hits));
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut rng = Rng::new(3);
+        let mut items = (0..50).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+    }
+}
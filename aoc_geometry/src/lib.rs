@@ -0,0 +1,244 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use aoc_math::gcd;
+
+/// Point on an integer plane. Matches grid layout: `x` is the column and `y` is the row, growing
+/// downwards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    #[must_use]
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Converts a `(row, column)` grid position.
+    #[must_use]
+    pub fn from_pos((row, col): (usize, usize)) -> Self {
+        // grids never come close to i64::MAX cells in either dimension
+        Self::new(col as i64, row as i64)
+    }
+
+    /// Converts back to a `(row, column)` grid position, unless any coordinate is negative.
+    #[must_use]
+    pub fn to_pos(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?))
+    }
+
+    #[must_use]
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.delta()
+    }
+
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    #[must_use]
+    pub fn chebyshev(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise from north.
+    pub const ALL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        match self {
+            Self::North => Self::West,
+            Self::East => Self::North,
+            Self::South => Self::East,
+            Self::West => Self::South,
+        }
+    }
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::East => Self::South,
+            Self::South => Self::West,
+            Self::West => Self::North,
+        }
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    /// Offset of a single step, with north pointing towards negative `y`.
+    #[must_use]
+    pub fn delta(self) -> Point {
+        match self {
+            Self::North => Point::new(0, -1),
+            Self::East => Point::new(1, 0),
+            Self::South => Point::new(0, 1),
+            Self::West => Point::new(-1, 0),
+        }
+    }
+}
+
+fn edges(vertices: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    vertices
+        .iter()
+        .copied()
+        .zip(vertices.iter().copied().cycle().skip(1))
+}
+
+/// Twice the area of a simple polygon, which keeps it integral for lattice vertices. Vertices
+/// can be listed in either orientation and the polygon is closed implicitly.
+#[must_use]
+pub fn double_area(vertices: &[Point]) -> u64 {
+    // shoelace formula
+    edges(vertices)
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<i64>()
+        .unsigned_abs()
+}
+
+/// Number of lattice points lying on the boundary of a polygon.
+#[must_use]
+pub fn boundary_points(vertices: &[Point]) -> u64 {
+    edges(vertices)
+        .map(|(a, b)| gcd(a.x.abs_diff(b.x), a.y.abs_diff(b.y)))
+        .sum()
+}
+
+/// Number of lattice points strictly inside a simple polygon, by Pick's theorem.
+#[must_use]
+pub fn interior_points(vertices: &[Point]) -> u64 {
+    if vertices.len() < 3 {
+        return 0;
+    }
+
+    // A = i + b/2 - 1  =>  i = (2A - b + 2) / 2
+    (double_area(vertices) + 2).saturating_sub(boundary_points(vertices)) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(side: i64) -> Vec<Point> {
+        vec![
+            Point::new(0, 0),
+            Point::new(side, 0),
+            Point::new(side, side),
+            Point::new(0, side),
+        ]
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(1, 6);
+        let b = Point::new(5, -2);
+        assert_eq!(a.manhattan(b), 12);
+        assert_eq!(a.chebyshev(b), 8);
+        assert_eq!(a.manhattan(a), 0);
+    }
+
+    #[test]
+    fn test_grid_positions() {
+        let p = Point::from_pos((2, 7));
+        assert_eq!(p, Point::new(7, 2));
+        assert_eq!(p.to_pos(), Some((2, 7)));
+        assert_eq!(p.step(Direction::North).to_pos(), Some((1, 7)));
+        assert_eq!(Point::ORIGIN.step(Direction::West).to_pos(), None);
+    }
+
+    #[test]
+    fn test_directions() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.opposite().opposite(), d);
+            assert_eq!(d.delta() + d.opposite().delta(), Point::ORIGIN);
+        }
+
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::East.opposite(), Direction::West);
+        assert_eq!(Direction::South.delta() * 3, Point::new(0, 3));
+    }
+
+    #[test]
+    fn test_polygon() {
+        assert_eq!(double_area(&square(4)), 32);
+        assert_eq!(boundary_points(&square(4)), 16);
+        assert_eq!(interior_points(&square(4)), 9);
+
+        let mut reversed = square(4);
+        reversed.reverse();
+        assert_eq!(double_area(&reversed), 32);
+
+        // right triangle with a diagonal hypotenuse through (2, 1) and (1, 2)
+        let triangle = [Point::new(0, 0), Point::new(3, 0), Point::new(0, 3)];
+        assert_eq!(double_area(&triangle), 9);
+        assert_eq!(boundary_points(&triangle), 9);
+        assert_eq!(interior_points(&triangle), 1);
+
+        assert_eq!(interior_points(&[]), 0);
+        assert_eq!(interior_points(&[Point::ORIGIN, Point::new(5, 0)]), 0);
+    }
+}
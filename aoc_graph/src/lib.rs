@@ -0,0 +1,492 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    error::Error,
+    fmt,
+    hash::Hash,
+    ops::Add,
+};
+
+// Graphs are implicit: they are given by a starting node (or a few of them) and a function
+// listing successors of a node, so nodes get discovered during the search.
+
+fn build_path<N: Clone + Eq + Hash>(parents: &HashMap<N, Option<N>>, goal: N) -> Vec<N> {
+    let mut path = vec![goal];
+
+    while let Some(Some(parent)) = path.last().and_then(|n| parents.get(n)) {
+        path.push(parent.clone());
+    }
+
+    path.reverse();
+    path
+}
+
+/// Shortest path, by number of edges, from `start` to the first node satisfying `is_goal`.
+/// The path includes both ends.
+pub fn bfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(build_path(&parents, node));
+        }
+
+        for next in successors(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Number of edges on the shortest path from `start` to every node reachable from it.
+pub fn bfs_distances<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// All nodes reachable from `start`, including itself.
+pub fn reachable<N, I>(start: N, successors: impl FnMut(&N) -> I) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs_distances(start, successors).into_keys().collect()
+}
+
+/// Cheapest path from `start` to the first node satisfying `is_goal`, along with its cost.
+/// Successors come with the cost of the edge leading to them, which can't be negative.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], guided by a `heuristic` estimating the remaining cost to a goal. The path
+/// is the cheapest one as long as the heuristic never overestimates and is consistent.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // nodes don't have to be ordered, so the queue refers to them by index
+    let mut nodes = vec![start.clone()];
+    let mut ids = HashMap::from([(start.clone(), 0)]);
+    // cheapest known cost and the node it's reached from
+    let mut best: Vec<(C, Option<usize>)> = vec![(C::default(), None)];
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+
+    while let Some(Reverse((_, cost, i))) = queue.pop() {
+        if cost > best[i].0 {
+            // stale entry, a cheaper way here was found after it had been queued
+            continue;
+        }
+
+        if is_goal(&nodes[i]) {
+            let mut path = vec![nodes[i].clone()];
+            let mut current = i;
+
+            while let Some(parent) = best[current].1 {
+                path.push(nodes[parent].clone());
+                current = parent;
+            }
+
+            path.reverse();
+            return Some((path, cost));
+        }
+
+        for (next, edge_cost) in successors(&nodes[i]) {
+            let next_cost = cost + edge_cost;
+
+            let j = match ids.get(&next) {
+                Some(&j) if next_cost >= best[j].0 => continue,
+                Some(&j) => {
+                    best[j] = (next_cost, Some(i));
+                    j
+                }
+                None => {
+                    nodes.push(next.clone());
+                    ids.insert(next.clone(), nodes.len() - 1);
+                    best.push((next_cost, Some(i)));
+                    nodes.len() - 1
+                }
+            };
+
+            queue.push(Reverse((next_cost + heuristic(&next), next_cost, j)));
+        }
+    }
+
+    None
+}
+
+/// Nodes forming a cycle, in order. The last node leads back to the first one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: fmt::Debug> fmt::Display for Cycle<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cycle: ")?;
+
+        for node in &self.0 {
+            write!(f, "{node:?} -> ")?;
+        }
+
+        match self.0.first() {
+            Some(first) => write!(f, "{first:?}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<N: fmt::Debug> Error for Cycle<N> {}
+
+/// Graph discovered from the roots, with nodes numbered in order of discovery.
+struct Discovered<N> {
+    nodes: Vec<N>,
+    successors: Vec<Vec<usize>>,
+    components: Vec<Vec<usize>>,
+}
+
+// Iterative Tarjan's algorithm, so deep graphs can't overflow the stack. Components are found
+// in reverse topological order: each one after all the components reachable from it.
+fn tarjan<N, I>(
+    roots: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Discovered<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    #[derive(Clone, Copy)]
+    struct State {
+        index: usize,
+        low: usize,
+        on_stack: bool,
+    }
+
+    let mut nodes = vec![];
+    let mut ids = HashMap::new();
+    let mut succ_ids: Vec<Vec<usize>> = vec![];
+    let mut states: Vec<Option<State>> = vec![];
+    let mut stack = vec![];
+    let mut components = vec![];
+    let mut counter = 0;
+
+    let mut id = |node: N, nodes: &mut Vec<N>, states: &mut Vec<Option<State>>| -> usize {
+        *ids.entry(node.clone()).or_insert_with(|| {
+            nodes.push(node);
+            states.push(None);
+            nodes.len() - 1
+        })
+    };
+
+    for root in roots {
+        let root = id(root, &mut nodes, &mut states);
+
+        if states[root].is_some() {
+            continue;
+        }
+
+        // node, position of the next successor to look at
+        let mut call_stack = vec![(root, 0)];
+
+        while let Some(&mut (v, ref mut pos)) = call_stack.last_mut() {
+            if states[v].is_none() {
+                states[v] = Some(State {
+                    index: counter,
+                    low: counter,
+                    on_stack: true,
+                });
+                counter += 1;
+                stack.push(v);
+
+                let succ = successors(&nodes[v])
+                    .into_iter()
+                    .map(|n| id(n, &mut nodes, &mut states))
+                    .collect();
+
+                if succ_ids.len() <= v {
+                    succ_ids.resize(v + 1, vec![]);
+                }
+                succ_ids[v] = succ;
+            }
+
+            if let Some(&w) = succ_ids[v].get(*pos) {
+                *pos += 1;
+
+                match states[w] {
+                    None => call_stack.push((w, 0)),
+                    Some(State {
+                        index,
+                        on_stack: true,
+                        ..
+                    }) => {
+                        let state = states[v].as_mut().expect("visited above");
+                        state.low = state.low.min(index);
+                    }
+                    Some(_) => {}
+                }
+
+                continue;
+            }
+
+            call_stack.pop();
+            let state = states[v].expect("visited above");
+
+            if let Some(&(u, _)) = call_stack.last() {
+                let parent = states[u].as_mut().expect("visited before its successors");
+                parent.low = parent.low.min(state.low);
+            }
+
+            if state.low == state.index {
+                let mut component = vec![];
+
+                while let Some(w) = stack.pop() {
+                    states[w].as_mut().expect("on stack").on_stack = false;
+                    component.push(w);
+
+                    if w == v {
+                        break;
+                    }
+                }
+
+                component.reverse();
+                components.push(component);
+            }
+        }
+    }
+
+    succ_ids.resize(nodes.len(), vec![]);
+
+    Discovered {
+        nodes,
+        successors: succ_ids,
+        components,
+    }
+}
+
+/// Strongly connected components of the graph reachable from `roots`. Every component comes
+/// after all the components reachable from it.
+pub fn strongly_connected_components<N, I>(
+    roots: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let Discovered {
+        nodes, components, ..
+    } = tarjan(roots, successors);
+
+    components
+        .into_iter()
+        .map(|c| c.into_iter().map(|i| nodes[i].clone()).collect())
+        .collect()
+}
+
+/// Orders the graph reachable from `roots` so that every node comes before its successors.
+///
+/// # Errors
+///
+/// Returns one of the cycles when the graph has any, as then no such order exists.
+pub fn topological_sort<N, I>(
+    roots: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> Result<Vec<N>, Cycle<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let Discovered {
+        nodes,
+        successors,
+        components,
+    } = tarjan(roots, successors);
+
+    let mut order = Vec::with_capacity(nodes.len());
+
+    for component in components.iter().rev() {
+        let v = component[0];
+
+        if component.len() == 1 && !successors[v].contains(&v) {
+            order.push(nodes[v].clone());
+            continue;
+        }
+
+        // every node of a component has a successor within it, so following those has to
+        // revisit some node eventually
+        let mut path = vec![v];
+        let mut seen = HashMap::from([(v, 0)]);
+        let mut current = v;
+
+        loop {
+            current = *successors[current]
+                .iter()
+                .find(|w| component.contains(w))
+                .expect("node of a cyclic component leads back into it");
+
+            if let Some(&start) = seen.get(&current) {
+                let cycle = path[start..].iter().map(|&i| nodes[i].clone()).collect();
+                return Err(Cycle(cycle));
+            }
+
+            seen.insert(current, path.len());
+            path.push(current);
+        }
+    }
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3
+    //  \-> 4 ---^
+    fn dag(n: &u8) -> Vec<u8> {
+        match n {
+            0 => vec![1, 4],
+            1 => vec![2],
+            2 | 4 => vec![3],
+            _ => vec![],
+        }
+    }
+
+    // {0, 1, 2} form a cycle leading to {3, 4}, which form another one, and 5 loops onto itself
+    fn cyclic(n: &u8) -> Vec<u8> {
+        match n {
+            0 => vec![1],
+            1 => vec![2],
+            2 => vec![0, 3],
+            3 => vec![4],
+            4 => vec![3],
+            5 => vec![5],
+            _ => vec![],
+        }
+    }
+
+    fn grid_successors(&(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        // 5x5 grid with a wall at x == 2, open only at y == 4
+        [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+            .into_iter()
+            .filter(|&(x, y)| (0..5).contains(&x) && (0..5).contains(&y))
+            .filter(|&(x, y)| x != 2 || y == 4)
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let path = bfs((0, 0), grid_successors, |&n| n == (4, 0)).unwrap();
+        assert_eq!(path.len() - 1, 12);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(4, 0)));
+        assert!(path
+            .windows(2)
+            .all(|w| grid_successors(&w[0]).contains(&w[1])));
+
+        assert_eq!(bfs(0, dag, |&n| n == 3), Some(vec![0, 4, 3]));
+        assert_eq!(bfs(1, dag, |&n| n == 0), None);
+
+        let distances = bfs_distances(0, dag);
+        assert_eq!(distances[&3], 2);
+        assert_eq!(distances.len(), 5);
+        assert_eq!(reachable(3, cyclic), HashSet::from([3, 4]));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // going around through 1 and 2 is cheaper than the direct edge
+        let weighted = |n: &u8| match n {
+            0 => vec![(1, 1), (3, 10)],
+            1 => vec![(2, 2)],
+            2 => vec![(3, 3)],
+            _ => vec![],
+        };
+
+        assert_eq!(
+            dijkstra(0, weighted, |&n| n == 3),
+            Some((vec![0, 1, 2, 3], 6))
+        );
+        assert_eq!(dijkstra(3, weighted, |&n| n == 0), None);
+        assert_eq!(dijkstra(2, weighted, |&n| n == 2), Some((vec![2], 0)));
+    }
+
+    #[test]
+    fn test_astar() {
+        let successors = |n: &(i32, i32)| grid_successors(n).into_iter().map(|m| (m, 1));
+        let manhattan = |&(x, y): &(i32, i32)| (4 - x).abs() + y.abs();
+
+        let (path, cost) = astar((0, 0), successors, manhattan, |&n| n == (4, 0)).unwrap();
+        assert_eq!(cost, 12);
+        assert_eq!(path.len(), 13);
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let mut components = strongly_connected_components([0, 5], cyclic);
+        components.iter_mut().for_each(|c| c.sort_unstable());
+        assert_eq!(components, vec![vec![3, 4], vec![0, 1, 2], vec![5]]);
+
+        assert_eq!(
+            strongly_connected_components([0], dag),
+            vec![vec![3], vec![2], vec![1], vec![4], vec![0]]
+        );
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let order = topological_sort([0], dag).unwrap();
+        let position = |n| order.iter().position(|&m| m == n).unwrap();
+        assert_eq!(order.len(), 5);
+        assert!((0..5).all(|n| dag(&n).into_iter().all(|m| position(n) < position(m))));
+
+        assert_eq!(topological_sort([3], cyclic), Err(Cycle(vec![3, 4])));
+        assert_eq!(topological_sort([5], cyclic), Err(Cycle(vec![5])));
+        assert_eq!(
+            topological_sort([0], cyclic).unwrap_err().to_string(),
+            "cycle: 0 -> 1 -> 2 -> 0"
+        );
+    }
+}
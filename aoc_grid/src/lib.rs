@@ -0,0 +1,422 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use anyhow::bail;
+use aoc_parse::{FromSpan, ParseError, Span};
+
+/// Position in a grid as `(row, column)`, with `(0, 0)` in the top left corner.
+pub type Pos = (usize, usize);
+
+const OFFSETS_4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// Rectangular map of cells, stored row by row in a single `Vec`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// # Errors
+    ///
+    /// Returns error when number of cells doesn't match the dimensions.
+    pub fn new(rows: usize, cols: usize, cells: Vec<T>) -> Result<Self, anyhow::Error> {
+        if rows * cols != cells.len() {
+            bail!("{} cells don't fit a {}x{} grid", cells.len(), rows, cols);
+        }
+
+        Ok(Self { cells, rows, cols })
+    }
+
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..rows)
+            .flat_map(|i| (0..cols).map(move |j| (i, j)))
+            .map(&mut f)
+            .collect();
+
+        Self { cells, rows, cols }
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[must_use]
+    pub fn contains(&self, (i, j): Pos) -> bool {
+        i < self.rows && j < self.cols
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.cols + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.0 * self.cols + pos.1])
+    }
+
+    /// Cells of row `i`, left to right.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `i` is out of bounds.
+    #[must_use]
+    pub fn row(&self, i: usize) -> &[T] {
+        assert!(i < self.rows, "row {i} out of bounds");
+        &self.cells[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// Cells of column `j`, top to bottom.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `j` is out of bounds.
+    pub fn column(&self, j: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(j < self.cols, "column {j} out of bounds");
+        // skipping rather than slicing keeps columns of a grid without rows empty
+        self.cells.iter().skip(j).step_by(self.cols)
+    }
+
+    pub fn iter_rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        (0..self.rows).map(|i| self.row(i))
+    }
+
+    pub fn iter_columns(&self) -> impl DoubleEndedIterator<Item = impl Iterator<Item = &T>> + '_ {
+        (0..self.cols).map(|j| self.column(j))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        let cols = self.cols;
+
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(k, cell)| ((k / cols, k % cols), cell))
+    }
+
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.cells
+            .iter()
+            .position(&mut predicate)
+            .map(|k| (k / self.cols, k % self.cols))
+    }
+
+    /// Up to 4 orthogonal neighbours of `pos` that lie within the grid, clockwise from north.
+    pub fn neighbours_4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &OFFSETS_4)
+    }
+
+    /// Up to 8 orthogonal and diagonal neighbours of `pos` that lie within the grid, clockwise
+    /// from north.
+    pub fn neighbours_8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &OFFSETS_8)
+    }
+
+    fn neighbours(
+        &self,
+        (i, j): Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + '_ {
+        offsets.iter().filter_map(move |&(di, dj)| {
+            let pos = (i.checked_add_signed(di)?, j.checked_add_signed(dj)?);
+            self.contains(pos).then_some(pos)
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    pub fn swap(&mut self, a: Pos, b: Pos) {
+        self.cells
+            .swap(a.0 * self.cols + a.1, b.0 * self.cols + b.1);
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    #[must_use]
+    pub fn filled(rows: usize, cols: usize, value: T) -> Self {
+        Self {
+            cells: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |(i, j)| self[(j, i)].clone())
+    }
+
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |(i, j)| {
+            self[(self.rows - 1 - j, i)].clone()
+        })
+    }
+
+    #[must_use]
+    pub fn rotate_counterclockwise(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |(i, j)| {
+            self[(j, self.cols - 1 - i)].clone()
+        })
+    }
+
+    /// Mirrors the grid left to right.
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self {
+        Self::from_fn(self.rows, self.cols, |(i, j)| {
+            self[(i, self.cols - 1 - j)].clone()
+        })
+    }
+
+    /// Mirrors the grid top to bottom.
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self {
+        Self::from_fn(self.rows, self.cols, |(i, j)| {
+            self[(self.rows - 1 - i, j)].clone()
+        })
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        let (rows, cols) = (self.rows, self.cols);
+
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} out of bounds of {rows}x{cols} grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        let (rows, cols) = (self.rows, self.cols);
+
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} out of bounds of {rows}x{cols} grid"))
+    }
+}
+
+/// Parses one row per line and one cell per character. All rows have to be equally long.
+impl<T> FromSpan for Grid<T>
+where
+    T: TryFrom<char>,
+    T::Error: fmt::Display,
+{
+    fn from_span(s: Span<'_>) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut rows = 0;
+        let mut cols = None;
+
+        for line in s.lines() {
+            let row = line.parse_chars()?;
+
+            match cols {
+                None => cols = Some(row.len()),
+                Some(cols) if cols != row.len() => {
+                    return Err(line.error(format_args!(
+                        "row has {} cells, expected {}",
+                        row.len(),
+                        cols
+                    )));
+                }
+                Some(_) => {}
+            }
+
+            cells.extend(row);
+            rows += 1;
+        }
+
+        Ok(Self {
+            cells,
+            rows,
+            cols: cols.unwrap_or(0),
+        })
+    }
+}
+
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+    T::Error: fmt::Display,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        aoc_parse::parse(s)
+    }
+}
+
+/// Writes the grid back in the text form it's parsed from, without a trailing newline.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.iter_rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "\
+abc
+def";
+
+    fn grid() -> Grid<char> {
+        TEST_INPUT.parse().unwrap()
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Cell {
+        Empty,
+        Wall,
+    }
+
+    impl TryFrom<char> for Cell {
+        type Error = anyhow::Error;
+
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            match c {
+                '.' => Ok(Self::Empty),
+                '#' => Ok(Self::Wall),
+                c => bail!("couldn't parse Cell from {:?}", c),
+            }
+        }
+    }
+
+    #[test]
+    fn test_grid_parsing() {
+        let g = grid();
+        assert_eq!((g.rows(), g.cols()), (2, 3));
+        assert_eq!(g[(0, 0)], 'a');
+        assert_eq!(g[(1, 2)], 'f');
+        assert_eq!(g.get((2, 0)), None);
+        assert_eq!(g.get((0, 3)), None);
+
+        let cells: Grid<Cell> = ".#\n#.".parse().unwrap();
+        assert_eq!(cells[(0, 1)], Cell::Wall);
+        assert_eq!(cells.position(|c| *c == Cell::Wall), Some((0, 1)));
+
+        let empty: Grid<char> = "".parse().unwrap();
+        assert_eq!((empty.rows(), empty.cols()), (0, 0));
+    }
+
+    #[test]
+    fn test_grid_parsing_errors() {
+        let e = ".#\n#x".parse::<Grid<Cell>>().unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 2: couldn't parse Cell from 'x'\n  #x\n   ^"
+        );
+        let e = "abc\nde".parse::<Grid<char>>().unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+        assert_eq!(e.message, "row has 2 cells, expected 3");
+        assert!(Grid::new(2, 2, vec![1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        assert_eq!(grid().to_string(), TEST_INPUT);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let g = grid();
+        assert_eq!(g.row(1), &['d', 'e', 'f']);
+        assert_eq!(g.column(1).collect::<String>(), "be");
+        assert_eq!(g.column(2).rev().collect::<String>(), "fc");
+        assert_eq!(
+            g.iter_columns()
+                .map(Iterator::collect::<String>)
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert_eq!(g.iter().nth(4), Some(((1, 1), &'e')));
+
+        let empty = Grid::filled(0, 3, 'x');
+        assert_eq!(empty.column(2).count(), 0);
+        assert_eq!(empty.iter_columns().flatten().count(), 0);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let g = grid();
+        assert_eq!(
+            g.neighbours_4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            g.neighbours_4((1, 1)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 2), (1, 0)]
+        );
+        assert_eq!(g.neighbours_8((0, 1)).count(), 5);
+        assert_eq!(g.neighbours_8((1, 2)).count(), 3);
+
+        let single: Grid<char> = "a".parse().unwrap();
+        assert_eq!(single.neighbours_8((0, 0)).count(), 0);
+
+        let column: Grid<char> = "a\nb\nc".parse().unwrap();
+        assert_eq!(
+            column.neighbours_4((1, 0)).collect::<Vec<_>>(),
+            vec![(0, 0), (2, 0)]
+        );
+    }
+
+    #[test]
+    fn test_transformations() {
+        let g = grid();
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(g.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(g.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(g.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(g.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(
+            g.rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise(),
+            g
+        );
+    }
+}
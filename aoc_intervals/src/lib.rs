@@ -0,0 +1,316 @@
+use std::ops::{Add, Range, Sub};
+
+/// Set of values stored as sorted, disjoint, non-adjacent half-open ranges.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: vec![] }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn normalized(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|r| r.start < r.end);
+        ranges.sort_unstable_by_key(|r| r.start);
+
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        Self { ranges: merged }
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(range);
+        *self = Self::normalized(ranges);
+    }
+
+    #[must_use]
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        // ranges are sorted, so find the last one starting at or before the value
+        let i = self.ranges.partition_point(|r| r.start <= value);
+        i > 0 && value < self.ranges[i - 1].end
+    }
+
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalized(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = vec![];
+
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            // whichever ends first can't overlap anything further in the other set
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut j = 0;
+
+        for a in &self.ranges {
+            let mut start = a.start;
+
+            // skip ranges of `other` that end before this one starts
+            while other.ranges.get(j).is_some_and(|b| b.end <= start) {
+                j += 1;
+            }
+
+            let mut k = j;
+
+            while let Some(b) = other.ranges.get(k).filter(|b| b.start < a.end) {
+                if start < b.start {
+                    ranges.push(start..b.start);
+                }
+
+                start = start.max(b.end);
+                k += 1;
+            }
+
+            if start < a.end {
+                ranges.push(start..a.end);
+            }
+        }
+
+        Self { ranges }
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// Number of values in the set.
+    #[must_use]
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::default(), |acc, r| acc + (r.end - r.start))
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::normalized(vec![range])
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::normalized(iter.into_iter().collect())
+    }
+}
+
+/// Splits `range` into consecutive pieces at every breakpoint lying strictly inside it.
+pub fn split<T: Copy + Ord>(
+    range: Range<T>,
+    breakpoints: impl IntoIterator<Item = T>,
+) -> Vec<Range<T>> {
+    let mut breakpoints = breakpoints
+        .into_iter()
+        .filter(|b| range.start < *b && *b < range.end)
+        .collect::<Vec<_>>();
+    breakpoints.sort_unstable();
+    breakpoints.dedup();
+
+    let mut pieces = Vec::with_capacity(breakpoints.len() + 1);
+    let mut start = range.start;
+
+    for b in breakpoints {
+        pieces.push(start..b);
+        start = b;
+    }
+
+    if start < range.end {
+        pieces.push(start..range.end);
+    }
+
+    pieces
+}
+
+/// Function moving values of each source range to consecutive values from the matching
+/// destination start, leaving values outside of all source ranges as they are. When source
+/// ranges overlap, the one added first wins.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PiecewiseMap<T> {
+    pieces: Vec<(Range<T>, T)>,
+}
+
+impl<T> Default for PiecewiseMap<T> {
+    fn default() -> Self {
+        Self { pieces: vec![] }
+    }
+}
+
+impl<T> PiecewiseMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, src: Range<T>, dst_start: T) {
+        self.pieces.push((src, dst_start));
+    }
+
+    /// Source ranges and their destination starts, in the order they were added.
+    #[must_use]
+    pub fn pieces(&self) -> &[(Range<T>, T)] {
+        &self.pieces
+    }
+
+    #[must_use]
+    pub fn map(&self, value: T) -> T {
+        self.pieces
+            .iter()
+            .find(|(src, _)| src.contains(&value))
+            .map_or(value, |(src, dst_start)| value - src.start + *dst_start)
+    }
+
+    /// Maps every value of the set at once, splitting its ranges wherever pieces begin or end.
+    #[must_use]
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut unmapped = set.clone();
+        let mut mapped = vec![];
+
+        for (src, dst_start) in &self.pieces {
+            let src = IntervalSet::from(src.clone());
+
+            for r in unmapped.intersection(&src).ranges() {
+                let start = r.start - src.ranges[0].start + *dst_start;
+                mapped.push(start..start + (r.end - r.start));
+            }
+
+            unmapped = unmapped.difference(&src);
+        }
+
+        mapped.extend(unmapped.ranges);
+
+        IntervalSet::normalized(mapped)
+    }
+}
+
+impl<T> FromIterator<(Range<T>, T)> for PiecewiseMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Range<T>, T)>>(iter: I) -> Self {
+        Self {
+            pieces: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<i64>]) -> IntervalSet<i64> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_normalization() {
+        let s = set(&[(10..15), (0..3), (3..5), (12..20), (7..7)]);
+        assert_eq!(s.ranges(), &[(0..5), (10..20)]);
+        assert_eq!(s.len(), 15);
+        assert_eq!(s.min(), Some(0));
+        assert!(s.contains(4));
+        assert!(!s.contains(5));
+        assert!(s.contains(10));
+        assert!(!s.contains(-1));
+        assert!(IntervalSet::<i64>::new().is_empty());
+
+        let mut s = s;
+        s.insert(5..10);
+        assert_eq!(s, IntervalSet::from(0..20));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0..10), (20..30)]);
+        let b = set(&[(5..25), (28..40)]);
+
+        assert_eq!(a.union(&b), IntervalSet::from(0..40));
+        assert_eq!(a.intersection(&b).ranges(), &[(5..10), (20..25), (28..30)]);
+        assert_eq!(a.difference(&b).ranges(), &[(0..5), (25..28)]);
+        assert_eq!(b.difference(&a).ranges(), &[(10..20), (30..40)]);
+        assert!(a.difference(&a).is_empty());
+        assert_eq!(a.difference(&IntervalSet::new()), a);
+        assert!(a.intersection(&IntervalSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_split() {
+        assert_eq!(
+            split(0..10, [5, 3, 0, 10, 12, 3]),
+            vec![(0..3), (3..5), (5..10)]
+        );
+        assert_eq!(split(0..10, []), vec![(0..10)]);
+        assert!(split(5..5, [5]).is_empty());
+    }
+
+    #[test]
+    fn test_piecewise_map() {
+        let map: PiecewiseMap<u64> = [(98..100, 50), (50..98, 52)].into_iter().collect();
+
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(14), 14);
+        assert_eq!(map.map(99), 51);
+        assert_eq!(map.pieces(), &[(98..100, 50), (50..98, 52)]);
+
+        let seeds = IntervalSet::from_iter([(79..93), (45..55), (95..101)]);
+        assert_eq!(
+            map.map_set(&seeds).ranges(),
+            &[(45..57), (81..95), (97..101)]
+        );
+        assert_eq!(map.map_set(&seeds).len(), seeds.len());
+    }
+}
@@ -0,0 +1,253 @@
+/// Greatest common divisor, with `gcd(0, 0) == 0`.
+#[must_use]
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// Least common multiple, or `None` when it doesn't fit in `u64`.
+#[must_use]
+pub fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Greatest common divisor of all numbers, `0` for an empty slice.
+#[must_use]
+pub fn gcd_all(numbers: &[u64]) -> u64 {
+    numbers.iter().fold(0, |acc, &n| gcd(acc, n))
+}
+
+/// Least common multiple of all numbers, `1` for an empty slice, or `None` on overflow.
+#[must_use]
+pub fn lcm_all(numbers: &[u64]) -> Option<u64> {
+    numbers.iter().try_fold(1, |acc, &n| checked_lcm(acc, n))
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g`, where `g` is the non-negative greatest
+/// common divisor of `a` and `b`.
+#[must_use]
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Inverse of `a` modulo `m` in `0..m`, if `a` and `m` are coprime and `m` is positive.
+#[must_use]
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    if m <= 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Solves a system of congruences `x ≡ r (mod m)`, given as `(r, m)` pairs. Moduli don't have to
+/// be pairwise coprime. Returns the smallest non-negative solution along with the modulus it
+/// repeats with (the lcm of all moduli).
+///
+/// Returns `None` when the congruences contradict each other, a modulus isn't positive, or the
+/// intermediate values don't fit in `i128`.
+#[must_use]
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(r1, m1): (i128, i128), &(r2, m2)| {
+            if m2 <= 0 {
+                return None;
+            }
+
+            // r1 + m1 * k ≡ r2 (mod m2)  <=>  m1 * k ≡ r2 - r1 (mod m2)
+            let (g, p, _) = extended_gcd(m1, m2);
+            let diff = r2.rem_euclid(m2) - r1;
+
+            if diff % g != 0 {
+                return None;
+            }
+
+            let m2 = m2 / g;
+            let k = ((diff / g).rem_euclid(m2))
+                .checked_mul(p.rem_euclid(m2))?
+                .rem_euclid(m2);
+            let m = m1.checked_mul(m2)?;
+            let r = m1.checked_mul(k)?.checked_add(r1)?.rem_euclid(m);
+
+            Some((r, m))
+        })
+}
+
+/// Largest `r` such that `r * r <= n`.
+#[must_use]
+pub fn isqrt_u128(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's method, starting from a power of two not smaller than the root, decreases
+    // monotonically until it reaches it
+    let bits = 128 - n.leading_zeros();
+    let mut x = 1 << bits.div_ceil(2);
+
+    loop {
+        let y = (x + n / x) / 2;
+
+        if y >= x {
+            return x;
+        }
+
+        x = y;
+    }
+}
+
+/// Largest `r` such that `r * r <= n`.
+#[must_use]
+pub fn isqrt_u64(n: u64) -> u64 {
+    // root of a u64 always fits in 32 bits
+    isqrt_u128(n.into()) as u64
+}
+
+/// Integers with overflow checked arithmetic, so sums and products can be checked generically.
+pub trait CheckedOps: Copy {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_ops {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedOps for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_ops!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Sum of all numbers, or `None` when it overflows.
+pub fn checked_sum<T: CheckedOps>(numbers: impl IntoIterator<Item = T>) -> Option<T> {
+    numbers
+        .into_iter()
+        .try_fold(T::ZERO, |acc, n| acc.checked_add(n))
+}
+
+/// Product of all numbers, or `None` when it overflows.
+pub fn checked_product<T: CheckedOps>(numbers: impl IntoIterator<Item = T>) -> Option<T> {
+    numbers
+        .into_iter()
+        .try_fold(T::ONE, |acc, n| acc.checked_mul(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(48, 18), 6);
+        assert_eq!(gcd(18, 48), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd_all(&[12, 18, 30]), 6);
+        assert_eq!(gcd_all(&[]), 0);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(checked_lcm(21, 6), Some(42));
+        assert_eq!(checked_lcm(0, 6), Some(0));
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(lcm_all(&[2, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all(&[]), Some(1));
+        assert_eq!(lcm_all(&[1 << 63, 3]), None);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (7, 0), (0, -7)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(a * x + b * y, g);
+            assert_eq!(
+                g,
+                i128::from(gcd(a.unsigned_abs() as u64, b.unsigned_abs() as u64))
+            );
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(2, 4), None);
+        assert_eq!(mod_inverse(2, 0), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(-1, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(1, 0)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..10_000u64 {
+            let r = isqrt_u64(n);
+            assert!(r * r <= n && n < (r + 1) * (r + 1), "isqrt({n}) = {r}");
+        }
+
+        assert_eq!(isqrt_u64(u64::MAX), u64::from(u32::MAX));
+        assert_eq!(isqrt_u64(1 << 62), 1 << 31);
+        assert_eq!(isqrt_u128(u128::MAX), u128::from(u64::MAX));
+        assert_eq!(isqrt_u128((1 << 100) - 1), (1 << 50) - 1);
+    }
+
+    #[test]
+    fn test_checked_sum_and_product() {
+        assert_eq!(checked_sum([1u64, 2, 3]), Some(6));
+        assert_eq!(checked_sum::<u8>([]), Some(0));
+        assert_eq!(checked_sum([200u8, 56]), None);
+        assert_eq!(checked_sum([-5i64, 3]), Some(-2));
+        assert_eq!(checked_product([2usize, 3, 7]), Some(42));
+        assert_eq!(checked_product::<u32>([]), Some(1));
+        assert_eq!(checked_product([1u64 << 32, 1 << 32]), None);
+    }
+}
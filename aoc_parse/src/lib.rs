@@ -0,0 +1,419 @@
+use std::{error::Error, fmt, str::FromStr};
+
+/// Error pointing at the place in the input that couldn't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// Whole input line the error points into.
+    pub snippet: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "  {}", self.snippet)?;
+        write!(f, "  {:>1$}", "^", self.column)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Part of the input being parsed. Remembers where it lies in the whole input, so errors can
+/// point at the exact line and column. Splitting a span yields spans again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span<'a> {
+    source: &'a str,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> Span<'a> {
+    #[must_use]
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            start: 0,
+            end: source.len(),
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        &self.source[self.start..self.end]
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    // `text` has to be a slice of this span, as returned by `str` methods called on it
+    fn sub(&self, text: &'a str) -> Self {
+        let start = text.as_ptr() as usize - self.source.as_ptr() as usize;
+
+        Self {
+            source: self.source,
+            start,
+            end: start + text.len(),
+        }
+    }
+
+    /// Error pointing at the start of the span.
+    #[must_use]
+    pub fn error(&self, message: impl fmt::Display) -> ParseError {
+        self.error_at(0, message)
+    }
+
+    /// Error pointing `offset` bytes into the span.
+    #[must_use]
+    pub fn error_at(&self, offset: usize, message: impl fmt::Display) -> ParseError {
+        let offset = (self.start + offset).min(self.end);
+        let before = &self.source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            snippet: self.source[line_start..]
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[must_use]
+    pub fn trim(&self) -> Self {
+        self.sub(self.as_str().trim())
+    }
+
+    pub fn lines(self) -> impl Iterator<Item = Span<'a>> {
+        self.as_str().lines().map(move |l| self.sub(l))
+    }
+
+    /// Groups of consecutive lines separated by one or more blank lines.
+    pub fn sections(self) -> impl Iterator<Item = Span<'a>> {
+        let mut lines = self.lines().peekable();
+
+        std::iter::from_fn(move || {
+            while lines.next_if(|l| l.as_str().trim().is_empty()).is_some() {}
+
+            let first = lines.next()?;
+            let mut last = first;
+
+            while let Some(l) = lines.next_if(|l| !l.as_str().trim().is_empty()) {
+                last = l;
+            }
+
+            Some(Span {
+                source: first.source,
+                start: first.start,
+                end: last.end,
+            })
+        })
+    }
+
+    pub fn split(self, separator: &'a str) -> impl Iterator<Item = Span<'a>> {
+        self.as_str().split(separator).map(move |p| self.sub(p))
+    }
+
+    /// Pieces separated by ASCII whitespace.
+    pub fn words(self) -> impl Iterator<Item = Span<'a>> {
+        self.as_str()
+            .split_ascii_whitespace()
+            .map(move |w| self.sub(w))
+    }
+
+    /// # Errors
+    ///
+    /// Returns error when the span doesn't contain `separator`.
+    pub fn split_once(&self, separator: &str) -> Result<(Self, Self), ParseError> {
+        let (a, b) = self
+            .as_str()
+            .split_once(separator)
+            .ok_or_else(|| self.error(format_args!("expected {separator:?}")))?;
+
+        Ok((self.sub(a), self.sub(b)))
+    }
+
+    /// # Panics
+    ///
+    /// Panics when `mid` doesn't lie on a character boundary within the span.
+    #[must_use]
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        let (a, b) = self.as_str().split_at(mid);
+
+        (self.sub(a), self.sub(b))
+    }
+
+    /// # Errors
+    ///
+    /// Returns error when the span doesn't start with `prefix`.
+    pub fn strip_prefix(&self, prefix: &str) -> Result<Self, ParseError> {
+        self.as_str()
+            .strip_prefix(prefix)
+            .map(|s| self.sub(s))
+            .ok_or_else(|| self.error(format_args!("expected {prefix:?}")))
+    }
+
+    /// # Errors
+    ///
+    /// Returns error when the span doesn't end with `suffix`.
+    pub fn strip_suffix(&self, suffix: &str) -> Result<Self, ParseError> {
+        self.as_str()
+            .strip_suffix(suffix)
+            .map(|s| self.sub(s))
+            .ok_or_else(|| {
+                let offset = self.as_str().trim_end().len();
+                self.error_at(offset, format_args!("expected {suffix:?}"))
+            })
+    }
+
+    /// # Errors
+    ///
+    /// Returns error when the whole span can't be parsed as `T`.
+    pub fn parse<T>(&self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.as_str()
+            .parse()
+            .map_err(|e| self.error(format_args!("couldn't parse {:?}: {}", self.as_str(), e)))
+    }
+
+    /// Parses every character of the span on its own.
+    ///
+    /// # Errors
+    ///
+    /// Returns error pointing at the first character that can't be converted.
+    pub fn parse_chars<T>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T: TryFrom<char>,
+        T::Error: fmt::Display,
+    {
+        self.as_str()
+            .char_indices()
+            .map(|(i, c)| T::try_from(c).map_err(|e| self.error_at(i, e)))
+            .collect()
+    }
+
+    /// # Errors
+    ///
+    /// Returns error when the span isn't exactly one character that can be converted to `T`.
+    pub fn parse_char<T>(&self) -> Result<T, ParseError>
+    where
+        T: TryFrom<char>,
+        T::Error: fmt::Display,
+    {
+        let mut chars = self.as_str().chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => T::try_from(c).map_err(|e| self.error(e)),
+            _ => Err(self.error(format_args!(
+                "expected a single character, found {:?}",
+                self.as_str()
+            ))),
+        }
+    }
+
+    /// Parses whitespace separated integers.
+    ///
+    /// # Errors
+    ///
+    /// Returns error pointing at the first token that isn't a valid `T`.
+    pub fn integers<T>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.words().map(|w| w.parse()).collect()
+    }
+
+    /// Splits `key=value` style lists, e.g. `x=1,m=2` with `","` and `"="` as separators.
+    ///
+    /// # Errors
+    ///
+    /// Returns error when any of the pairs lacks `kv_separator`.
+    pub fn key_values(
+        &self,
+        pair_separator: &'a str,
+        kv_separator: &str,
+    ) -> Result<Vec<(Self, Self)>, ParseError> {
+        self.split(pair_separator)
+            .map(|pair| pair.split_once(kv_separator))
+            .collect()
+    }
+}
+
+/// Like `FromStr`, but reports errors pointing into the whole input.
+pub trait FromSpan: Sized {
+    /// # Errors
+    ///
+    /// Returns error pointing at the part of the span that couldn't be parsed.
+    fn from_span(s: Span<'_>) -> Result<Self, ParseError>;
+}
+
+/// Parses the whole string, so types implementing [`FromSpan`] can implement `FromStr` in one
+/// line.
+///
+/// # Errors
+///
+/// Returns error when `T` can't be parsed from the string.
+pub fn parse<T: FromSpan>(s: &str) -> Result<T, ParseError> {
+    T::from_span(Span::new(s))
+}
+
+/// Input as parsers expect it, whatever editor it went through: without a byte order mark, with
+/// `\n` line endings, no whitespace at the ends of lines and no blank lines at the end.
+#[must_use]
+pub fn normalize(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+
+    // trimming takes carriage returns of CRLF line endings along with other trailing whitespace
+    let mut lines = input.split('\n').map(str::trim_end).collect::<Vec<_>>();
+
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "\
+seeds: 79 14
+
+a-to-b map:
+50 98 2
+
+
+b-to-c map:
+0 15 37
+37 520 2";
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Rating(Vec<(char, u32)>);
+
+    impl FromSpan for Rating {
+        fn from_span(s: Span<'_>) -> Result<Self, ParseError> {
+            let ratings = s
+                .strip_prefix("{")?
+                .strip_suffix("}")?
+                .key_values(",", "=")?
+                .into_iter()
+                .map(|(k, v)| Ok((k.parse_char()?, v.parse()?)))
+                .collect::<Result<_, ParseError>>()?;
+
+            Ok(Self(ratings))
+        }
+    }
+
+    #[test]
+    fn test_splitting() {
+        let s = Span::new(TEST_INPUT);
+        let sections = s.sections().map(|s| s.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            sections,
+            [
+                "seeds: 79 14",
+                "a-to-b map:\n50 98 2",
+                "b-to-c map:\n0 15 37\n37 520 2"
+            ]
+        );
+
+        let seeds = s.lines().next().unwrap().strip_prefix("seeds: ").unwrap();
+        assert_eq!(seeds.integers::<u32>().unwrap(), [79, 14]);
+
+        let (name, _) = s.sections().nth(1).unwrap().split_once(" map:").unwrap();
+        assert_eq!(name.as_str(), "a-to-b");
+        assert_eq!(Span::new("").sections().count(), 0);
+        assert_eq!(Span::new("  a  ").trim().as_str(), "a");
+    }
+
+    #[test]
+    fn test_error_location() {
+        let s = Span::new(TEST_INPUT);
+        let line = s.lines().nth(8).unwrap();
+        let e = line.integers::<u8>().unwrap_err();
+        assert_eq!((e.line, e.column), (9, 4));
+        assert_eq!(e.snippet, "37 520 2");
+        assert_eq!(
+            e.to_string(),
+            "line 9, column 4: couldn't parse \"520\": number too large to fit in target type\n  \
+             37 520 2\n     ^"
+        );
+
+        let e = s.split_once("=").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 1));
+        assert_eq!(e.message, "expected \"=\"");
+
+        let e = Span::new("ab\ncé?").parse_chars::<Digit>().unwrap_err();
+        assert_eq!((e.line, e.column), (1, 1));
+        let line = Span::new("12\n3é?").lines().nth(1).unwrap();
+        let e = line.parse_chars::<Digit>().unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.message.as_str()),
+            (2, 2, "not a digit: 'é'")
+        );
+    }
+
+    #[derive(Debug)]
+    struct Digit;
+
+    impl TryFrom<char> for Digit {
+        type Error = String;
+
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            if c.is_ascii_digit() {
+                Ok(Self)
+            } else {
+                Err(format!("not a digit: {c:?}"))
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_span() {
+        assert_eq!(
+            parse::<Rating>("{x=787,m=2655}").unwrap(),
+            Rating(vec![('x', 787), ('m', 2655)])
+        );
+
+        let e = parse::<Rating>("{x=787,m=26a5}").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 10));
+        let e = parse::<Rating>("{x=787,m2655}").unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.message.as_str()),
+            (1, 8, "expected \"=\"")
+        );
+        let e = parse::<Rating>("{x=787,m=2655").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 14));
+        let e = parse::<Rating>("{xy=787}").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 2));
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(TEST_INPUT), TEST_INPUT);
+
+        let crlf = format!("\u{feff}{}\r\n\r\n", TEST_INPUT.replace('\n', "\r\n"));
+        assert_eq!(normalize(&crlf), TEST_INPUT);
+
+        assert_eq!(normalize("a  \n \t\nb\t\n\n  \n"), "a\n\nb");
+        assert_eq!(normalize("  a\n  b"), "  a\n  b");
+        assert_eq!(normalize("\n\r\n"), "");
+    }
+}
@@ -1,6 +1,12 @@
-use std::str::FromStr;
+use std::{io::BufRead, str::FromStr};
 
-#[derive(Debug)]
+use anyhow::anyhow;
+use aoc_core::{stream::try_fold_lines, trace, Answer, Part, Solution};
+use aoc_math::checked_sum;
+
+pub mod reference;
+
+#[derive(Debug, PartialEq, Eq)]
 pub struct Problem {
     lines: Vec<String>,
 }
@@ -9,104 +15,131 @@ impl FromStr for Problem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = &aoc_parse::normalize(s);
+
         Ok(Problem {
             lines: s.lines().map(std::borrow::ToOwned::to_owned).collect(),
         })
     }
 }
 
-#[must_use]
-pub fn solve_part_1(p: &Problem) -> u32 {
-    let Problem { lines } = p;
-
+fn calibration_value(line: &str) -> u32 {
     let to_digit = |c: &char| c.to_digit(10);
 
-    lines
-        .iter()
-        .map(|l| {
-            let nums = l.chars().filter(char::is_ascii_digit).collect::<Vec<_>>();
+    let nums = line
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<Vec<_>>();
 
-            let a = nums.first().and_then(to_digit).unwrap_or(0);
-            let b = nums.last().and_then(to_digit).unwrap_or(0);
+    let a = nums.first().and_then(to_digit).unwrap_or(0);
+    let b = nums.last().and_then(to_digit).unwrap_or(0);
 
-            a * 10 + b
-        })
-        .sum()
+    trace!("{line}: {a} and {b} make {}", a * 10 + b);
+    a * 10 + b
 }
 
-#[must_use]
-pub fn solve_part_2(p: &Problem) -> u32 {
-    let word_digits = (1u32..)
-        .zip([
-            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-        ])
-        .collect::<Vec<_>>();
-
+const WORD_DIGITS: [(u32, &str); 9] = [
+    (1, "one"),
+    (2, "two"),
+    (3, "three"),
+    (4, "four"),
+    (5, "five"),
+    (6, "six"),
+    (7, "seven"),
+    (8, "eight"),
+    (9, "nine"),
+];
+
+fn calibration_value_with_words(line: &str) -> u32 {
     let to_digit_with_idx = |&(idx, c): &(usize, char)| Some((idx, c.to_digit(10).unwrap_or(0)));
 
-    let mut result = 0;
-
-    for line in &p.lines {
-        let first_word_digit_idx = word_digits
-            .iter()
-            .filter_map(|&(val, word)| line.find(word).map(|idx| (idx, val)))
-            .min();
-
-        let last_word_digit_idx = word_digits
-            .iter()
-            .filter_map(|&(val, word)| line.rfind(word).map(|idx| (idx, val)))
-            .max();
-
-        let digit_chars = line
-            .chars()
-            .enumerate()
-            .filter(|(_, c)| c.is_ascii_digit())
-            .collect::<Vec<_>>();
-
-        let first_real_digit_idx = digit_chars.first().and_then(to_digit_with_idx);
-        let last_real_digit_idx = digit_chars.last().and_then(to_digit_with_idx);
-
-        let a = match (first_word_digit_idx, first_real_digit_idx) {
-            (None, None) => 0,
-            (None, Some((_, v))) | (Some((_, v)), None) => v,
-            (Some((idx_a, val_a)), Some((idx_b, val_b))) => {
-                if idx_a < idx_b {
-                    val_a
-                } else {
-                    val_b
-                }
+    let first_word_digit_idx = WORD_DIGITS
+        .iter()
+        .filter_map(|&(val, word)| line.find(word).map(|idx| (idx, val)))
+        .min();
+
+    let last_word_digit_idx = WORD_DIGITS
+        .iter()
+        .filter_map(|&(val, word)| line.rfind(word).map(|idx| (idx, val)))
+        .max();
+
+    // byte offsets, like those `find` gives for words
+    let digit_chars = line
+        .char_indices()
+        .filter(|(_, c)| c.is_ascii_digit())
+        .collect::<Vec<_>>();
+
+    let first_real_digit_idx = digit_chars.first().and_then(to_digit_with_idx);
+    let last_real_digit_idx = digit_chars.last().and_then(to_digit_with_idx);
+
+    let a = match (first_word_digit_idx, first_real_digit_idx) {
+        (None, None) => 0,
+        (None, Some((_, v))) | (Some((_, v)), None) => v,
+        (Some((idx_a, val_a)), Some((idx_b, val_b))) => {
+            if idx_a < idx_b {
+                val_a
+            } else {
+                val_b
             }
-        };
-
-        let b = match (last_word_digit_idx, last_real_digit_idx) {
-            (None, None) => 0,
-            (None, Some((_, v))) | (Some((_, v)), None) => v,
-            (Some((idx_a, val_a)), Some((idx_b, val_b))) => {
-                if idx_a > idx_b {
-                    val_a
-                } else {
-                    val_b
-                }
+        }
+    };
+
+    let b = match (last_word_digit_idx, last_real_digit_idx) {
+        (None, None) => 0,
+        (None, Some((_, v))) | (Some((_, v)), None) => v,
+        (Some((idx_a, val_a)), Some((idx_b, val_b))) => {
+            if idx_a > idx_b {
+                val_a
+            } else {
+                val_b
             }
-        };
+        }
+    };
 
-        result += a * 10 + b;
-    }
+    trace!("{line}: {a} and {b} make {}", a * 10 + b);
+    a * 10 + b
+}
 
-    result
+/// # Errors
+///
+/// Returns error when the sum overflows u32.
+pub fn solve_part_1(p: &Problem) -> Result<u32, anyhow::Error> {
+    checked_sum(p.lines.iter().map(|l| calibration_value(l)))
+        .ok_or_else(|| anyhow!("sum of calibration values overflows u32"))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// # Errors
+///
+/// Returns error when the sum overflows u32.
+pub fn solve_part_2(p: &Problem) -> Result<u32, anyhow::Error> {
+    checked_sum(p.lines.iter().map(|l| calibration_value_with_words(l)))
+        .ok_or_else(|| anyhow!("sum of calibration values overflows u32"))
+}
+
+/// Either part, reading the input a line at a time instead of parsing it whole.
+///
+/// # Errors
+///
+/// Returns error when the input can't be read or the sum overflows u32.
+pub fn stream(input: &mut dyn BufRead, part: Part) -> Result<u32, anyhow::Error> {
+    let value = match part {
+        Part::One => calibration_value,
+        Part::Two => calibration_value_with_words,
+    };
+
+    try_fold_lines(input, 0u32, |sum, line| {
+        sum.checked_add(value(line))
+            .ok_or_else(|| anyhow!("sum of calibration values overflows u32"))
+    })
+}
 
-    const TEST_INPUT: &str = "\
+const TEST_INPUT: &str = "\
 1abc2
 pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet";
 
-    const TEST_INPUT_2: &str = "\
+const TEST_INPUT_2: &str = "\
 two1nine
 eightwothree
 abcone2threexyz
@@ -115,15 +148,76 @@ xtwone3four
 zoneight234
 7pqrstsixteen";
 
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = 1;
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT_2];
+
+    fn part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_1(p)?.into())
+    }
+
+    fn part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_2(p)?.into())
+    }
+
+    fn reference_part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_1(p).into())
+    }
+
+    fn reference_part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_2(p).into())
+    }
+
+    fn stream(input: &mut dyn BufRead, part: Part) -> Option<Result<Answer, anyhow::Error>> {
+        Some(stream(input, part).map(Answer::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_solve_part_1() {
         let p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve_part_1(&p), 142);
+        assert_eq!(solve_part_1(&p).unwrap(), 142);
     }
 
     #[test]
     fn test_solve_part_2() {
         let p: Problem = TEST_INPUT_2.parse().unwrap();
-        assert_eq!(solve_part_2(&p), 281);
+        assert_eq!(solve_part_2(&p).unwrap(), 281);
+
+        let p: Problem = "\u{e9}\u{e9}\u{e9}\u{e9}one2".parse().unwrap();
+        assert_eq!(solve_part_2(&p).unwrap(), 12);
+    }
+
+    #[test]
+    fn test_explain() {
+        let p: Problem = TEST_INPUT_2.parse().unwrap();
+        let (_, notes) = trace::collect(|| solve_part_2(&p));
+        assert_eq!(notes.len(), 7);
+        assert_eq!(notes[1], "eightwothree: 8 and 3 make 83");
+    }
+
+    #[test]
+    fn test_stream() {
+        assert_eq!(stream(&mut TEST_INPUT.as_bytes(), Part::One).unwrap(), 142);
+        assert_eq!(
+            stream(&mut TEST_INPUT_2.as_bytes(), Part::Two).unwrap(),
+            281
+        );
+    }
+
+    #[test]
+    fn test_input_with_bom() {
+        let input = format!("\u{feff}{}\r\n\r\n", TEST_INPUT_2.replace('\n', "\r\n"));
+        let p: Problem = input.parse().unwrap();
+        assert_eq!(p.lines[0], "two1nine");
+        assert_eq!(solve_part_2(&p).unwrap(), 281);
     }
 }
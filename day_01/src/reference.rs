@@ -0,0 +1,74 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
+
+use crate::Problem;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn calibration_value(digits: &[u32]) -> u32 {
+    match (digits.first(), digits.last()) {
+        (Some(a), Some(b)) => a * 10 + b,
+        _ => 0,
+    }
+}
+
+#[must_use]
+pub fn solve_part_1(p: &Problem) -> u32 {
+    p.lines
+        .iter()
+        .map(|line| {
+            let digits = line
+                .chars()
+                .filter_map(|c| c.to_digit(10))
+                .collect::<Vec<_>>();
+            calibration_value(&digits)
+        })
+        .sum()
+}
+
+/// Tries every position of every line for a digit or a spelled out one.
+#[must_use]
+pub fn solve_part_2(p: &Problem) -> u32 {
+    p.lines
+        .iter()
+        .map(|line| {
+            let mut digits = vec![];
+
+            for (i, c) in line.char_indices() {
+                if let Some(d) = c.to_digit(10) {
+                    digits.push(d);
+                }
+
+                for (d, word) in (1..).zip(WORDS) {
+                    if line[i..].starts_with(word) {
+                        digits.push(d);
+                    }
+                }
+            }
+
+            calibration_value(&digits)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TEST_INPUT, TEST_INPUT_2};
+
+    #[test]
+    fn test_solve_part_1() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_1(&p), 142);
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p: Problem = TEST_INPUT_2.parse().unwrap();
+        assert_eq!(solve_part_2(&p), 281);
+
+        let p: Problem = "\u{e9}\u{e9}\u{e9}\u{e9}one2".parse().unwrap();
+        assert_eq!(solve_part_2(&p), 12);
+    }
+}
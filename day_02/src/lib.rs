@@ -1,22 +1,25 @@
-use std::str::FromStr;
+use std::{io::BufRead, str::FromStr};
 
-use anyhow::bail;
+use anyhow::anyhow;
+use aoc_core::{stream::try_fold_lines, trace, Answer, Part, Solution};
+use aoc_math::{checked_product, checked_sum};
+use aoc_parse::{FromSpan, ParseError, Span};
+
+pub mod reference;
 
 #[derive(Debug, Default, PartialEq, Eq)]
 struct SubSet(usize, usize, usize); // red, green, blue
 
-impl FromStr for SubSet {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+impl FromSpan for SubSet {
+    fn from_span(s: Span<'_>) -> Result<Self, ParseError> {
         let mut subset = SubSet::default();
 
         for color_count in s.split(", ") {
-            let (count, color) = color_count.split_once(' ').expect("malformed color count");
+            let (count, color) = color_count.split_once(" ")?;
 
             let count = count.parse()?;
 
-            match color {
+            match color.as_str() {
                 "red" => {
                     subset.0 = count;
                 }
@@ -26,7 +29,7 @@ impl FromStr for SubSet {
                 "blue" => {
                     subset.2 = count;
                 }
-                c => bail!("unknown color {}", c),
+                c => return Err(color.error(format_args!("unknown color {c:?}"))),
             }
         }
 
@@ -34,34 +37,44 @@ impl FromStr for SubSet {
     }
 }
 
+impl FromStr for SubSet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        aoc_parse::parse(s)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 struct Game {
     id: usize,
     subsets: Vec<SubSet>,
 }
 
-impl FromStr for Game {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (game_id_str, subsets) = s.split_once(": ").expect("malformed game");
+impl FromSpan for Game {
+    fn from_span(s: Span<'_>) -> Result<Self, ParseError> {
+        let (game_id, subsets) = s.split_once(": ")?;
 
-        let id = game_id_str
-            .split_once(' ')
-            .expect("malformed game prefix")
-            .1
-            .parse::<usize>()?;
+        let id = game_id.strip_prefix("Game ")?.parse()?;
 
         let subsets = subsets
             .split("; ")
-            .map(str::parse)
+            .map(SubSet::from_span)
             .collect::<Result<_, _>>()?;
 
         Ok(Game { id, subsets })
     }
 }
 
-#[derive(Debug)]
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        aoc_parse::parse(s)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
 pub struct Problem {
     games: Vec<Game>,
 }
@@ -70,56 +83,128 @@ impl FromStr for Problem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let games = s.lines().map(str::parse).collect::<Result<_, _>>()?;
+        let s = &aoc_parse::normalize(s);
+
+        let games = Span::new(s)
+            .lines()
+            .map(Game::from_span)
+            .collect::<Result<_, _>>()?;
 
         Ok(Problem { games })
     }
 }
 
-#[must_use]
-pub fn solve_part_1(p: &Problem) -> usize {
-    let Problem { games } = p;
+fn possible_id(Game { id, subsets }: &Game) -> Option<usize> {
+    let impossible = subsets
+        .iter()
+        .position(|&SubSet(r, g, b)| r > 12 || g > 13 || b > 14);
+
+    match impossible {
+        Some(i) => {
+            let SubSet(r, g, b) = subsets[i];
+            trace!(
+                "game {id}: draw {} of {r} red, {g} green and {b} blue is impossible",
+                i + 1
+            );
+            None
+        }
+        None => {
+            trace!("game {id}: possible");
+            Some(*id)
+        }
+    }
+}
 
-    games
+fn power(Game { subsets, .. }: &Game) -> Option<usize> {
+    let (max_r, max_g, max_b) = subsets
         .iter()
-        .filter_map(|Game { id, subsets }| {
-            subsets
-                .iter()
-                .all(|&SubSet(r, g, b)| r <= 12 && g <= 13 && b <= 14)
-                .then_some(id)
-        })
-        .sum()
+        .fold((0, 0, 0), |(x, y, z), &SubSet(r, g, b)| {
+            (x.max(r), y.max(g), z.max(b))
+        });
+
+    checked_product([max_r, max_g, max_b])
 }
 
-#[must_use]
-pub fn solve_part_2(p: &Problem) -> usize {
+/// # Errors
+///
+/// Returns error when the sum of game ids overflows.
+pub fn solve_part_1(p: &Problem) -> Result<usize, anyhow::Error> {
     let Problem { games } = p;
 
-    games
-        .iter()
-        .map(|Game { subsets, .. }| {
-            let (max_r, max_g, max_b) = subsets
-                .iter()
-                .fold((0, 0, 0), |(x, y, z), &SubSet(r, g, b)| {
-                    (x.max(r), y.max(g), z.max(b))
-                });
-
-            max_r * max_g * max_b
-        })
-        .sum()
+    checked_sum(games.iter().filter_map(possible_id))
+        .ok_or_else(|| anyhow!("sum of game ids overflows usize"))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// # Errors
+///
+/// Returns error when the powers of the sets of cubes or their sum overflow.
+pub fn solve_part_2(p: &Problem) -> Result<usize, anyhow::Error> {
+    let Problem { games } = p;
+
+    let powers = games.iter().map(power).collect::<Option<Vec<_>>>();
+
+    powers
+        .and_then(checked_sum)
+        .ok_or_else(|| anyhow!("sum of powers overflows usize"))
+}
 
-    const TEST_INPUT: &str = "\
+/// Either part, reading the input a game at a time instead of parsing it whole.
+///
+/// # Errors
+///
+/// Returns error when the input can't be read, a game can't be parsed or the sum overflows.
+pub fn stream(input: &mut dyn BufRead, part: Part) -> Result<usize, anyhow::Error> {
+    try_fold_lines(input, 0usize, |sum, line| {
+        let game = aoc_parse::parse::<Game>(line)?;
+
+        match part {
+            Part::One => possible_id(&game).map_or(Some(sum), |id| sum.checked_add(id)),
+            Part::Two => power(&game).and_then(|power| sum.checked_add(power)),
+        }
+        .ok_or_else(|| anyhow!("sum overflows usize"))
+    })
+}
+
+const TEST_INPUT: &str = "\
 Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = 2;
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT];
+
+    fn part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_1(p)?.into())
+    }
+
+    fn part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_2(p)?.into())
+    }
+
+    fn reference_part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_1(p).into())
+    }
+
+    fn reference_part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_2(p).into())
+    }
+
+    fn stream(input: &mut dyn BufRead, part: Part) -> Option<Result<Answer, anyhow::Error>> {
+        Some(stream(input, part).map(Answer::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_subset_parsing() {
         assert_eq!("3 blue, 4 red".parse::<SubSet>().unwrap(), SubSet(4, 0, 3));
@@ -145,17 +230,67 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
                 subsets: vec![SubSet(6, 3, 1), SubSet(1, 2, 2)]
             }
         );
+
+        let e = "Game 1: 3 blue\nGame 2: 4 purple, 1 red"
+            .parse::<Problem>()
+            .unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 11: unknown color \"purple\"\n  Game 2: 4 purple, 1 red\n            ^"
+        );
+        assert!("Game x: 1 red".parse::<Game>().is_err());
+        assert!("Game 1: 1red".parse::<Game>().is_err());
     }
 
     #[test]
     fn test_solve_part_1() {
         let p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve_part_1(&p), 8);
+        assert_eq!(solve_part_1(&p).unwrap(), 8);
     }
 
     #[test]
     fn test_solve_part_2() {
         let p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve_part_2(&p), 2286);
+        assert_eq!(solve_part_2(&p).unwrap(), 2286);
+    }
+
+    #[test]
+    fn test_explain() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        let (_, notes) = trace::collect(|| solve_part_1(&p));
+        assert_eq!(
+            notes,
+            [
+                "game 1: possible",
+                "game 2: possible",
+                "game 3: draw 1 of 20 red, 8 green and 6 blue is impossible",
+                "game 4: draw 3 of 14 red, 3 green and 15 blue is impossible",
+                "game 5: possible",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stream() {
+        assert_eq!(stream(&mut TEST_INPUT.as_bytes(), Part::One).unwrap(), 8);
+        assert_eq!(stream(&mut TEST_INPUT.as_bytes(), Part::Two).unwrap(), 2286);
+
+        // errors point at the same spot as when parsing the whole input
+        let input = "Game 1: 3 blue\nGame 2: 4 purple, 1 red";
+        assert_eq!(
+            stream(&mut input.as_bytes(), Part::One)
+                .unwrap_err()
+                .to_string(),
+            input.parse::<Problem>().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_input_with_bom() {
+        // the mark would stick to "Game" of the first game
+        let input = format!("\u{feff}{}\r\n", TEST_INPUT.replace('\n', "\r\n"));
+        let p: Problem = input.parse().unwrap();
+        assert_eq!(p.games.len(), 5);
+        assert_eq!(solve_part_2(&p).unwrap(), 2286);
     }
 }
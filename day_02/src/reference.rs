@@ -0,0 +1,61 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
+
+use crate::{Game, Problem, SubSet};
+
+#[must_use]
+pub fn solve_part_1(p: &Problem) -> usize {
+    let mut sum = 0;
+
+    for Game { id, subsets } in &p.games {
+        let mut possible = true;
+
+        for &SubSet(red, green, blue) in subsets {
+            if red > 12 || green > 13 || blue > 14 {
+                possible = false;
+            }
+        }
+
+        if possible {
+            sum += id;
+        }
+    }
+
+    sum
+}
+
+#[must_use]
+pub fn solve_part_2(p: &Problem) -> usize {
+    let mut sum = 0;
+
+    for Game { subsets, .. } in &p.games {
+        let (mut red, mut green, mut blue) = (0, 0, 0);
+
+        for subset in subsets {
+            red = red.max(subset.0);
+            green = green.max(subset.1);
+            blue = blue.max(subset.2);
+        }
+
+        sum += red * green * blue;
+    }
+
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_INPUT;
+
+    #[test]
+    fn test_solve_part_1() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_1(&p), 8);
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_2(&p), 2286);
+    }
+}
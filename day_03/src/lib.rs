@@ -3,39 +3,51 @@ use std::{
     str::FromStr,
 };
 
-#[derive(Debug, Default, PartialEq, Eq)]
+use anyhow::anyhow;
+use aoc_core::{Answer, Layout, Solution};
+use aoc_grid::Grid;
+use aoc_math::checked_sum;
+use aoc_parse::Span;
+
+pub mod reference;
+
+#[derive(Debug, PartialEq, Eq)]
 pub struct Problem {
     // position, part number, length
     numbers: Vec<((usize, usize), u32, usize)>,
-    // position, symbol
-    symbols: HashMap<(usize, usize), char>,
+    schematic: Grid<char>,
 }
 
 impl FromStr for Problem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut p = Problem::default();
-
-        for (x, line) in s.lines().enumerate() {
-            let number_slices = find_number_slices(line);
-            let numbers = number_slices
-                .iter()
-                .map(|(y, s)| ((x, *y), s.parse::<u32>().unwrap(), s.len()));
+        let s = &aoc_parse::normalize(s);
 
-            p.numbers.extend(numbers);
+        let schematic: Grid<char> = s.parse()?;
+        let mut numbers = Vec::new();
 
-            let symbols = line
-                .char_indices()
-                .filter_map(|(y, c)| (c != '.' && !c.is_ascii_digit()).then_some(((x, y), c)));
+        for (x, line) in Span::new(s).lines().enumerate() {
+            for (offset, number) in find_number_slices(line.as_str()) {
+                let value = number.parse().map_err(|e| {
+                    line.error_at(offset, format_args!("couldn't parse {number:?}: {e}"))
+                })?;
+                // the schematic is indexed by char, offsets are in bytes
+                let y = line.as_str()[..offset].chars().count();
 
-            p.symbols.extend(symbols);
+                numbers.push(((x, y), value, number.len()));
+            }
         }
 
-        Ok(p)
+        Ok(Self { numbers, schematic })
     }
 }
 
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+// Numbers of `s` with their byte offsets.
 fn find_number_slices(s: &str) -> Vec<(usize, String)> {
     let mut i = 0;
     let mut v = Vec::new();
@@ -56,60 +68,37 @@ fn find_number_slices(s: &str) -> Vec<(usize, String)> {
     v
 }
 
-fn neighbour_offsets(i: usize, j: usize) -> &'static [(isize, isize)] {
-    match (i, j) {
-        (0, 0) => &[(0, 1), (1, 0), (1, 1)],
-        (0, _) => &[(0, -1), (0, 1), (1, -1), (1, 0), (1, 1)],
-        (_, 0) => &[(-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)],
-        (_, _) => &[
-            (-1, 0),
-            (1, 0),
-            (0, -1),
-            (0, 1),
-            (-1, -1),
-            (-1, 1),
-            (1, 1),
-            (1, -1),
-        ],
-    }
-}
+/// # Errors
+///
+/// Returns error when the sum of part numbers overflows.
+pub fn solve_part_1(p: &Problem) -> Result<u32, anyhow::Error> {
+    let Problem { numbers, schematic } = p;
 
-#[must_use]
-pub fn solve_part_1(p: &Problem) -> u32 {
-    let Problem { numbers, symbols } = p;
+    let part_numbers = numbers.iter().filter_map(|&((x, y), number, len)| {
+        (y..(y + len))
+            .flat_map(|y| schematic.neighbours_8((x, y)))
+            .any(|k| is_symbol(schematic[k]))
+            .then_some(number)
+    });
 
-    numbers
-        .iter()
-        .filter_map(|&((x, y), number, len)| {
-            (y..(y + len))
-                .flat_map(|y| {
-                    neighbour_offsets(x, y).iter().map(move |&(dx, dy)| {
-                        ((x as isize + dx) as usize, (y as isize + dy) as usize)
-                    })
-                })
-                .any(|k| symbols.contains_key(&k))
-                .then_some(number)
-        })
-        .sum()
+    checked_sum(part_numbers).ok_or_else(|| anyhow!("sum of part numbers overflows u32"))
 }
 
-#[must_use]
-pub fn solve_part_2(p: &Problem) -> u32 {
-    let Problem { numbers, symbols } = p;
+/// # Errors
+///
+/// Returns error when the sum of gear ratios overflows.
+pub fn solve_part_2(p: &Problem) -> Result<u64, anyhow::Error> {
+    let Problem { numbers, schematic } = p;
 
-    let mut possible_gears = symbols
+    let mut possible_gears = schematic
         .iter()
         .filter(|&(_, &c)| c == '*')
-        .map(|(&pos, _)| (pos, vec![]))
+        .map(|(pos, _)| (pos, vec![]))
         .collect::<HashMap<(usize, usize), Vec<u32>>>();
 
     for &((x, y), number, len) in numbers {
         let neighbour_gear_indices = (y..(y + len))
-            .flat_map(|y| {
-                neighbour_offsets(x, y)
-                    .iter()
-                    .map(move |&(dx, dy)| ((x as isize + dx) as usize, (y as isize + dy) as usize))
-            })
+            .flat_map(|y| schematic.neighbours_8((x, y)))
             .filter(|k| possible_gears.contains_key(k))
             .collect::<HashSet<(usize, usize)>>();
 
@@ -120,18 +109,15 @@ pub fn solve_part_2(p: &Problem) -> u32 {
         }
     }
 
-    possible_gears
+    let gear_ratios = possible_gears
         .iter()
         .filter(|&(_, nums)| nums.len() == 2)
-        .map(|(_, nums)| nums[0] * nums[1])
-        .sum()
-}
+        .map(|(_, nums)| u64::from(nums[0]) * u64::from(nums[1]));
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    checked_sum(gear_ratios).ok_or_else(|| anyhow!("sum of gear ratios overflows u64"))
+}
 
-    const TEST_INPUT: &str = "\
+const TEST_INPUT: &str = "\
 467..114..
 ...*......
 ..35..633.
@@ -143,6 +129,38 @@ mod tests {
 ...$.*....
 .664.598..";
 
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = 3;
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT];
+    const LAYOUT: Layout = Layout::Grid;
+
+    fn part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_1(p)?.into())
+    }
+
+    fn part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_2(p)?.into())
+    }
+
+    fn reference_part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_1(p).into())
+    }
+
+    fn reference_part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_2(p).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_parse::ParseError;
+
+    use super::*;
+
     #[test]
     fn test_find_number_slices() {
         let s = "543.*...&231..123";
@@ -161,41 +179,69 @@ mod tests {
     fn test_problem_parsing() {
         let p: Problem = TEST_INPUT.parse().unwrap();
         assert_eq!(
-            p,
-            Problem {
-                numbers: vec![
-                    ((0, 0), 467, 3),
-                    ((0, 5), 114, 3),
-                    ((2, 2), 35, 2),
-                    ((2, 6), 633, 3),
-                    ((4, 0), 617, 3),
-                    ((5, 7), 58, 2),
-                    ((6, 2), 592, 3),
-                    ((7, 6), 755, 3),
-                    ((9, 1), 664, 3),
-                    ((9, 5), 598, 3)
-                ],
-                symbols: HashMap::from([
-                    ((8, 5), '*'),
-                    ((1, 3), '*'),
-                    ((3, 6), '#'),
-                    ((4, 3), '*'),
-                    ((5, 5), '+'),
-                    ((8, 3), '$')
-                ])
-            }
+            p.numbers,
+            vec![
+                ((0, 0), 467, 3),
+                ((0, 5), 114, 3),
+                ((2, 2), 35, 2),
+                ((2, 6), 633, 3),
+                ((4, 0), 617, 3),
+                ((5, 7), 58, 2),
+                ((6, 2), 592, 3),
+                ((7, 6), 755, 3),
+                ((9, 1), 664, 3),
+                ((9, 5), 598, 3)
+            ]
+        );
+        assert_eq!(
+            p.schematic
+                .iter()
+                .filter(|&(_, &c)| is_symbol(c))
+                .map(|(pos, &c)| (pos, c))
+                .collect::<HashMap<_, _>>(),
+            HashMap::from([
+                ((8, 5), '*'),
+                ((1, 3), '*'),
+                ((3, 6), '#'),
+                ((4, 3), '*'),
+                ((5, 5), '+'),
+                ((8, 3), '$')
+            ])
         );
+
+        let e = "............\n.9999999999*"
+            .parse::<Problem>()
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!((e.line, e.column), (2, 2));
+    }
+
+    #[test]
+    fn test_non_ascii_input() {
+        let p: Problem = "ü12.\n*...\n3...".parse().unwrap();
+        assert_eq!(p.numbers, vec![((0, 1), 12, 2), ((2, 0), 3, 1)]);
+        assert_eq!(solve_part_2(&p).unwrap(), 36);
+        assert_eq!(reference::solve_part_2(&p), 36);
     }
 
     #[test]
     fn test_solve_part_1() {
         let p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve_part_1(&p), 4361);
+        assert_eq!(solve_part_1(&p).unwrap(), 4361);
     }
 
     #[test]
     fn test_solve_part_2() {
         let p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve_part_2(&p), 467_835);
+        assert_eq!(solve_part_2(&p).unwrap(), 467_835);
+    }
+
+    #[test]
+    fn test_input_with_bom() {
+        // the mark would be a symbol next to the number starting the input
+        let p: Problem = "\u{feff}12...\r\n.....\r\n".parse().unwrap();
+        assert_eq!(p.schematic.cols(), 5);
+        assert_eq!(solve_part_1(&p).unwrap(), 0);
     }
 }
@@ -0,0 +1,102 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
+
+use aoc_grid::Grid;
+
+use crate::{is_symbol, Problem};
+
+/// Row, first and last column, and value of every number, read off the schematic one cell at a
+/// time.
+fn numbers(schematic: &Grid<char>) -> Vec<(usize, usize, usize, u64)> {
+    let mut numbers = vec![];
+
+    for i in 0..schematic.rows() {
+        let mut current: Option<(usize, u64)> = None;
+
+        for j in 0..=schematic.cols() {
+            let digit = schematic.get((i, j)).and_then(|c| c.to_digit(10));
+
+            current = match (current, digit) {
+                (None, Some(d)) => Some((j, u64::from(d))),
+                (Some((start, value)), Some(d)) => Some((start, value * 10 + u64::from(d))),
+                (Some((start, value)), None) => {
+                    numbers.push((i, start, j - 1, value));
+                    None
+                }
+                (None, None) => None,
+            };
+        }
+    }
+
+    numbers
+}
+
+fn is_adjacent((i, first, last, _): (usize, usize, usize, u64), (x, y): (usize, usize)) -> bool {
+    x + 1 >= i && x <= i + 1 && y + 1 >= first && y <= last + 1
+}
+
+#[must_use]
+pub fn solve_part_1(p: &Problem) -> u64 {
+    let numbers = numbers(&p.schematic);
+    let mut sum = 0;
+
+    for number in numbers {
+        if p.schematic
+            .iter()
+            .any(|(pos, &c)| is_symbol(c) && is_adjacent(number, pos))
+        {
+            sum += number.3;
+        }
+    }
+
+    sum
+}
+
+#[must_use]
+pub fn solve_part_2(p: &Problem) -> u64 {
+    let numbers = numbers(&p.schematic);
+    let mut sum = 0;
+
+    for (pos, &c) in p.schematic.iter() {
+        if c != '*' {
+            continue;
+        }
+
+        let adjacent = numbers
+            .iter()
+            .filter(|&&number| is_adjacent(number, pos))
+            .collect::<Vec<_>>();
+
+        if let [a, b] = adjacent[..] {
+            sum += a.3 * b.3;
+        }
+    }
+
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_INPUT;
+
+    #[test]
+    fn test_numbers() {
+        let p: Problem = "12.\n..3\n4..".parse().unwrap();
+        assert_eq!(
+            numbers(&p.schematic),
+            vec![(0, 0, 1, 12), (1, 2, 2, 3), (2, 0, 0, 4)]
+        );
+    }
+
+    #[test]
+    fn test_solve_part_1() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_1(&p), 4361);
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_2(&p), 467_835);
+    }
+}
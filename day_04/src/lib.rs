@@ -1,40 +1,43 @@
-use std::{collections::HashSet, str::FromStr};
+use std::{
+    collections::{HashSet, VecDeque},
+    io::BufRead,
+    str::FromStr,
+};
 
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
+use aoc_core::{stream::try_fold_lines, Answer, Part, Solution};
+use aoc_math::checked_sum;
+use aoc_parse::{FromSpan, ParseError, Span};
 
-#[derive(Debug)]
+pub mod reference;
+
+#[derive(Debug, PartialEq, Eq)]
 struct Card {
     winning: HashSet<u8>,
     numbers: HashSet<u8>,
 }
 
-impl FromStr for Card {
-    type Err = anyhow::Error;
+impl FromSpan for Card {
+    fn from_span(s: Span<'_>) -> Result<Self, ParseError> {
+        let (card_winning, chosen) = s.split_once(" | ")?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (card_winning, chosen) = s
-            .split_once(" | ")
-            .ok_or_else(|| anyhow!("couldn't split at ' | '"))?;
-
-        let (_, winning) = card_winning
-            .split_once(": ")
-            .ok_or_else(|| anyhow!("couldn't split a ': '"))?;
+        let (_, winning) = card_winning.split_once(": ")?;
 
         let winning = winning
-            .split_ascii_whitespace()
-            .map(str::parse)
+            .words()
+            .map(|w| w.parse())
             .collect::<Result<_, _>>()?;
 
         let numbers = chosen
-            .split_ascii_whitespace()
-            .map(str::parse)
+            .words()
+            .map(|w| w.parse())
             .collect::<Result<_, _>>()?;
 
         Ok(Self { winning, numbers })
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Problem {
     cards: Vec<Card>,
 }
@@ -43,33 +46,45 @@ impl FromStr for Problem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = &aoc_parse::normalize(s);
+
         Ok(Self {
-            cards: s.lines().map(str::parse).collect::<Result<_, _>>()?,
+            cards: Span::new(s)
+                .lines()
+                .map(Card::from_span)
+                .collect::<Result<_, _>>()?,
         })
     }
 }
 
+fn points(Card { winning, numbers }: &Card) -> Option<u64> {
+    match winning.intersection(numbers).count() {
+        0 => Some(0),
+        common => 2u64.checked_pow(u32::try_from(common - 1).ok()?),
+    }
+}
+
 /// # Errors
 ///
-/// Returns error when count of intersection of winning and chosen numbers can't be casted down to
-/// u32.
+/// Returns error when points of a card or their sum overflow u64.
 pub fn solve_part_1(p: &Problem) -> Result<u64, anyhow::Error> {
     let Problem { cards } = p;
-    let mut result = 0;
+    let mut result = 0u64;
 
-    for Card { winning, numbers } in cards {
-        let common: u32 = winning.intersection(numbers).count().try_into()?;
-
-        if common > 0 {
-            result += 2u64.pow(common.saturating_sub(1));
-        }
+    for (i, card) in cards.iter().enumerate() {
+        result = points(card)
+            .and_then(|points| result.checked_add(points))
+            .ok_or_else(|| anyhow!("points of card {} overflow u64", i + 1))?;
     }
 
     Ok(result)
 }
 
-#[must_use]
-pub fn solve_part_2(p: &Problem) -> u64 {
+/// # Errors
+///
+/// Returns error when a card wins copies of cards past the end of the table, or the number of
+/// copies overflows u64.
+pub fn solve_part_2(p: &Problem) -> Result<u64, anyhow::Error> {
     let Problem { cards } = p;
     let mut copies = vec![1u64; cards.len()];
 
@@ -81,19 +96,115 @@ pub fn solve_part_2(p: &Problem) -> u64 {
         }
 
         let times = copies[i];
-        for copy in &mut copies[(i + 1)..=(i + common)] {
-            *copy += times;
+        let Some(won) = copies.get_mut((i + 1)..=(i + common)) else {
+            bail!(
+                "card {} wins copies of cards past the end of the table",
+                i + 1
+            );
+        };
+
+        for copy in won {
+            *copy = copy
+                .checked_add(times)
+                .ok_or_else(|| anyhow!("number of copies overflows u64"))?;
         }
     }
 
-    copies.iter().sum()
+    checked_sum(copies).ok_or_else(|| anyhow!("number of cards overflows u64"))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Part 2 for cards coming one at a time. Only copies of the cards yet to come are kept, so
+/// memory is bound by the most numbers a card can match rather than by the number of cards.
+#[derive(Debug, Default)]
+struct Copies {
+    cards: usize,
+    total: u64,
+    upcoming: VecDeque<u64>,
+    // cards winning copies further than any before them, and past the cards seen so far; the
+    // first of them is at fault if the table ends here
+    overreaching: VecDeque<(usize, usize)>,
+}
+
+impl Copies {
+    fn add(mut self, Card { winning, numbers }: &Card) -> Result<Self, anyhow::Error> {
+        let overflow = || anyhow!("number of copies overflows u64");
+
+        self.cards += 1;
+        let copies = self
+            .upcoming
+            .pop_front()
+            .unwrap_or(0)
+            .checked_add(1)
+            .ok_or_else(overflow)?;
+        self.total = self
+            .total
+            .checked_add(copies)
+            .ok_or_else(|| anyhow!("number of cards overflows u64"))?;
+
+        while self
+            .overreaching
+            .front()
+            .is_some_and(|&(_, reach)| reach <= self.cards)
+        {
+            self.overreaching.pop_front();
+        }
+
+        let common = winning.intersection(numbers).count();
+
+        if common > 0 {
+            let reach = self.cards + common;
+            if self.overreaching.back().map_or(true, |&(_, r)| r < reach) {
+                self.overreaching.push_back((self.cards, reach));
+            }
+
+            if self.upcoming.len() < common {
+                self.upcoming.resize(common, 0);
+            }
+            for copy in self.upcoming.iter_mut().take(common) {
+                *copy = copy.checked_add(copies).ok_or_else(overflow)?;
+            }
+        }
+
+        Ok(self)
+    }
+
+    fn finish(self) -> Result<u64, anyhow::Error> {
+        if let Some(&(card, _)) = self.overreaching.front() {
+            bail!(
+                "card {} wins copies of cards past the end of the table",
+                card
+            );
+        }
+
+        Ok(self.total)
+    }
+}
+
+/// Either part, reading the input a card at a time instead of parsing it whole.
+///
+/// # Errors
+///
+/// Returns error when the input can't be read or a card can't be parsed, otherwise the same
+/// errors as [`solve_part_1`] and [`solve_part_2`].
+pub fn stream(input: &mut dyn BufRead, part: Part) -> Result<u64, anyhow::Error> {
+    match part {
+        Part::One => try_fold_lines(input, (0, 0u64), |(i, sum), line| {
+            let card = aoc_parse::parse::<Card>(line)?;
+            let sum = points(&card)
+                .and_then(|points| sum.checked_add(points))
+                .ok_or_else(|| anyhow!("points of card {} overflow u64", i + 1))?;
 
-    const TEST_INPUT: &str = "\
+            Ok((i + 1, sum))
+        })
+        .map(|(_, sum)| sum),
+        Part::Two => try_fold_lines(input, Copies::default(), |copies, line| {
+            copies.add(&aoc_parse::parse::<Card>(line)?)
+        })?
+        .finish(),
+    }
+}
+
+const TEST_INPUT: &str = "\
 Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
 Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
@@ -101,6 +212,63 @@ Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = 4;
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT];
+
+    fn part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_1(p)?.into())
+    }
+
+    fn part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_2(p)?.into())
+    }
+
+    fn reference_part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_1(p).into())
+    }
+
+    fn reference_part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_2(p)?.into())
+    }
+
+    fn stream(input: &mut dyn BufRead, part: Part) -> Option<Result<Answer, anyhow::Error>> {
+        Some(stream(input, part).map(Answer::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsing_errors() {
+        let e = "Card 1: 41 48 | 83 86\nCard 2: 13 256 | 61"
+            .parse::<Problem>()
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!((e.line, e.column), (2, 12));
+
+        let e = "Card 1: 41 48 83 86".parse::<Problem>().unwrap_err();
+        assert!(e
+            .to_string()
+            .starts_with("line 1, column 1: expected \" | \""));
+    }
+
+    #[test]
+    fn test_winning_past_the_end() {
+        let p: Problem = "Card 1: 1 2 | 1 3\nCard 2: 3 | 4".parse().unwrap();
+        assert_eq!(solve_part_2(&p).unwrap(), 3);
+
+        let p: Problem = "Card 1: 1 2 | 1 2\nCard 2: 3 | 4".parse().unwrap();
+        assert!(solve_part_2(&p).is_err());
+    }
+
     #[test]
     fn test_solve_part_1() {
         let p: Problem = TEST_INPUT.parse().unwrap();
@@ -110,6 +278,33 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
     #[test]
     fn test_solve_part_2() {
         let p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve_part_2(&p), 30);
+        assert_eq!(solve_part_2(&p).unwrap(), 30);
+    }
+
+    #[test]
+    fn test_stream() {
+        assert_eq!(stream(&mut TEST_INPUT.as_bytes(), Part::One).unwrap(), 13);
+        assert_eq!(stream(&mut TEST_INPUT.as_bytes(), Part::Two).unwrap(), 30);
+
+        let input = "Card 1: 1 2 | 1 3\nCard 2: 3 | 4";
+        assert_eq!(stream(&mut input.as_bytes(), Part::Two).unwrap(), 3);
+
+        // card 2 reaches past the end first, though card 3 reaches further
+        let input = "Card 1: 1 | 1\nCard 2: 1 2 | 1 2\nCard 3: 1 2 3 | 1 2 3";
+        assert_eq!(
+            stream(&mut input.as_bytes(), Part::Two)
+                .unwrap_err()
+                .to_string(),
+            solve_part_2(&input.parse().unwrap())
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_trailing_blank_lines() {
+        let p: Problem = format!("{TEST_INPUT}\n \n\t\n").parse().unwrap();
+        assert_eq!(p.cards.len(), 6);
+        assert_eq!(solve_part_1(&p).unwrap(), 13);
     }
 }
@@ -0,0 +1,74 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
+
+use anyhow::bail;
+
+use crate::{Card, Problem};
+
+fn matches(Card { winning, numbers }: &Card) -> usize {
+    numbers.iter().filter(|n| winning.contains(n)).count()
+}
+
+#[must_use]
+pub fn solve_part_1(p: &Problem) -> u64 {
+    let mut sum = 0;
+
+    for card in &p.cards {
+        let mut points = 0;
+
+        for _ in 0..matches(card) {
+            points = if points == 0 { 1 } else { points * 2 };
+        }
+
+        sum += points;
+    }
+
+    sum
+}
+
+/// Scratches every single copy of every card, one at a time.
+///
+/// # Errors
+///
+/// Returns error when a card wins copies of cards past the end of the table.
+pub fn solve_part_2(p: &Problem) -> Result<u64, anyhow::Error> {
+    let mut pile = (0..p.cards.len()).collect::<Vec<_>>();
+    let mut scratched = 0;
+
+    while let Some(i) = pile.pop() {
+        scratched += 1;
+
+        for j in (i + 1)..=(i + matches(&p.cards[i])) {
+            if j >= p.cards.len() {
+                bail!(
+                    "card {} wins copies of cards past the end of the table",
+                    i + 1
+                );
+            }
+
+            pile.push(j);
+        }
+    }
+
+    Ok(scratched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_INPUT;
+
+    #[test]
+    fn test_solve_part_1() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_1(&p), 13);
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_2(&p).unwrap(), 30);
+
+        let p: Problem = "Card 1: 1 2 | 1 2".parse().unwrap();
+        assert!(solve_part_2(&p).is_err());
+    }
+}
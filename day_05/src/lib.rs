@@ -1,6 +1,11 @@
 use std::{ops::Range, str::FromStr};
 
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
+use aoc_core::{trace, Answer, Layout, Solution};
+use aoc_intervals::{IntervalSet, PiecewiseMap};
+use aoc_parse::{FromSpan, ParseError, Span};
+
+pub mod reference;
 
 #[derive(Debug, PartialEq, Eq)]
 struct Mapping {
@@ -9,58 +14,69 @@ struct Mapping {
     length: usize,
 }
 
-impl FromStr for Mapping {
-    type Err = anyhow::Error;
+impl FromSpan for Mapping {
+    fn from_span(s: Span<'_>) -> Result<Self, ParseError> {
+        let [dst_range_start, src_range_start, length] = s.integers::<usize>()?[..] else {
+            return Err(s.error("expected destination start, source start and length"));
+        };
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let [dst_range_start, src_range_start, length]: [usize; 3] = s
-            .split_ascii_whitespace()
-            .map(str::parse)
-            .collect::<Result<Vec<usize>, _>>()?[..3]
-            .try_into()?;
+        let (Some(src_range_end), Some(dst_range_end)) = (
+            src_range_start.checked_add(length),
+            dst_range_start.checked_add(length),
+        ) else {
+            return Err(s.error("range doesn't fit in usize"));
+        };
 
         Ok(Self {
-            src_range: (src_range_start..(src_range_start + length)),
-            dst_range: (dst_range_start..(dst_range_start + length)),
+            src_range: (src_range_start..src_range_end),
+            dst_range: (dst_range_start..dst_range_end),
             length,
         })
     }
 }
 
-#[derive(Debug)]
+impl FromStr for Mapping {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        aoc_parse::parse(s)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
 pub struct Problem {
     seeds: Vec<usize>,
-    mappings: Vec<Vec<Mapping>>,
+    mappings: Vec<PiecewiseMap<usize>>,
 }
 
 impl FromStr for Problem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split("\n\n");
-
-        let seeds = parts.next().ok_or_else(|| anyhow!("no seeds"))?;
-        let seeds = seeds
-            .strip_prefix("seeds: ")
-            .ok_or_else(|| anyhow!("unexpected seeds prefix"))?;
-        let seeds = seeds
-            .split_ascii_whitespace()
-            .map(str::parse)
-            .collect::<Result<Vec<_>, _>>()?;
+        let s = &aoc_parse::normalize(s);
 
-        let mappings = parts
+        let s = Span::new(s);
+        let mut sections = s.sections();
+
+        let seeds = sections.next().ok_or_else(|| s.error("no seeds"))?;
+        let seeds = seeds.strip_prefix("seeds: ")?.integers()?;
+
+        let mappings = sections
             .map(|mapping_description| {
-                let (_, mappings) = mapping_description.split_once(":\n").ok_or_else(|| {
-                    anyhow!(
-                        "couldn't split mapping description: {:?}",
-                        mapping_description
-                    )
-                })?;
+                let (_, mappings) = mapping_description.split_once(":\n")?;
 
                 mappings
                     .lines()
-                    .map(str::parse)
-                    .collect::<Result<Vec<_>, _>>()
+                    .map(|l| {
+                        let Mapping {
+                            src_range,
+                            dst_range,
+                            ..
+                        } = Mapping::from_span(l)?;
+
+                        Ok((src_range, dst_range.start))
+                    })
+                    .collect::<Result<_, ParseError>>()
             })
             .collect::<Result<Vec<_>, _>>()?;
 
@@ -73,29 +89,57 @@ impl FromStr for Problem {
 /// Returns error when `seeds` vector is empty and therefore minimal location can't be found.
 pub fn solve_part_1(p: &Problem) -> Result<usize, anyhow::Error> {
     let Problem { seeds, mappings } = p;
-    let mut locations = seeds.clone();
 
-    for mapping in mappings {
-        for location in &mut locations {
-            if let Some(map) = mapping.iter().find(|a| a.src_range.contains(location)) {
-                *location -= map.src_range.start;
-                *location += map.dst_range.start;
+    seeds
+        .iter()
+        .map(|&seed| {
+            if trace::enabled() {
+                let values = mappings
+                    .iter()
+                    .scan(seed, |value, m| {
+                        *value = m.map(*value);
+                        Some(value.to_string())
+                    })
+                    .collect::<Vec<_>>();
+                trace!("seed {seed} -> {}", values.join(" -> "));
             }
-        }
+
+            mappings.iter().fold(seed, |location, m| m.map(location))
+        })
+        .min()
+        .ok_or_else(|| anyhow!("couldn't find min"))
+}
+
+/// Seeds come in `start length` pairs now, so whole ranges of them are mapped at once, each
+/// mapping splitting them wherever its source ranges begin or end.
+///
+/// # Errors
+///
+/// Returns error when seeds don't come in pairs, their ranges overflow, or there are none.
+pub fn solve_part_2(p: &Problem) -> Result<usize, anyhow::Error> {
+    let Problem { seeds, mappings } = p;
+
+    if seeds.len() % 2 != 0 {
+        bail!("seeds don't come in pairs: {:?}", seeds);
     }
 
-    locations
+    let seeds = seeds
+        .chunks(2)
+        .map(|pair| {
+            let end = pair[0].checked_add(pair[1])?;
+            Some(pair[0]..end)
+        })
+        .collect::<Option<IntervalSet<_>>>()
+        .ok_or_else(|| anyhow!("seed range doesn't fit in usize"))?;
+
+    mappings
         .iter()
+        .fold(seeds, |locations, m| m.map_set(&locations))
         .min()
-        .copied()
         .ok_or_else(|| anyhow!("couldn't find min"))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "\
+const TEST_INPUT: &str = "\
 seeds: 79 14 55 13
 
 seed-to-soil map:
@@ -130,6 +174,36 @@ humidity-to-location map:
 60 56 37
 56 93 4";
 
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = 5;
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT];
+    const LAYOUT: Layout = Layout::Sections { header: 1 };
+
+    fn part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_1(p)?.into())
+    }
+
+    fn part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_2(p)?.into())
+    }
+
+    fn reference_part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_1(p)?.into())
+    }
+
+    fn reference_part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_2(p)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_mapping_parsing() {
         assert_eq!(
@@ -140,6 +214,16 @@ humidity-to-location map:
                 length: 2
             }
         );
+        assert!("50 98".parse::<Mapping>().is_err());
+        assert!("50 98 2 1".parse::<Mapping>().is_err());
+        assert!("50 18446744073709551615 2".parse::<Mapping>().is_err());
+
+        let e = "seeds: 1 2\n\nseed-to-soil map:\n50 98 -2"
+            .parse::<Problem>()
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!((e.line, e.column), (4, 7));
     }
 
     #[test]
@@ -147,4 +231,30 @@ humidity-to-location map:
         let p: Problem = TEST_INPUT.parse().unwrap();
         assert_eq!(solve_part_1(&p).unwrap(), 35);
     }
+
+    #[test]
+    fn test_explain() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        let (_, notes) = trace::collect(|| solve_part_1(&p));
+        assert_eq!(
+            notes[0],
+            "seed 79 -> 81 -> 81 -> 81 -> 74 -> 78 -> 78 -> 82"
+        );
+        assert_eq!(notes.len(), 4);
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_2(&p).unwrap(), 46);
+    }
+
+    #[test]
+    fn test_crlf_input() {
+        // sections are still told apart by the blank lines between them
+        let p: Problem = TEST_INPUT.replace('\n', "\r\n").parse().unwrap();
+        assert_eq!(p.seeds, vec![79, 14, 55, 13]);
+        assert_eq!(p.mappings.len(), 7);
+        assert_eq!(solve_part_2(&p).unwrap(), 46);
+    }
 }
@@ -0,0 +1,104 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
+
+use std::ops::Range;
+
+use anyhow::{anyhow, bail};
+use aoc_intervals::{split, PiecewiseMap};
+
+use crate::Problem;
+
+// the first piece containing the value wins
+fn map(m: &PiecewiseMap<usize>, value: usize) -> usize {
+    for (src, dst_start) in m.pieces() {
+        if src.contains(&value) {
+            return dst_start + (value - src.start);
+        }
+    }
+
+    value
+}
+
+/// # Errors
+///
+/// Returns error when there are no seeds.
+pub fn solve_part_1(p: &Problem) -> Result<usize, anyhow::Error> {
+    let mut locations = vec![];
+
+    for &seed in &p.seeds {
+        let mut value = seed;
+
+        for m in &p.mappings {
+            value = map(m, value);
+        }
+
+        locations.push(value);
+    }
+
+    locations
+        .into_iter()
+        .min()
+        .ok_or_else(|| anyhow!("couldn't find min"))
+}
+
+/// Cuts seed ranges at every end of every source range, so each piece moves as a whole, then maps
+/// the pieces by their first value.
+///
+/// # Errors
+///
+/// Returns error when seeds don't come in pairs, their ranges overflow, or there are none.
+pub fn solve_part_2(p: &Problem) -> Result<usize, anyhow::Error> {
+    if p.seeds.len() % 2 != 0 {
+        bail!("seeds don't come in pairs: {:?}", p.seeds);
+    }
+
+    let mut ranges: Vec<Range<usize>> = vec![];
+
+    for pair in p.seeds.chunks(2) {
+        let end = pair[0]
+            .checked_add(pair[1])
+            .ok_or_else(|| anyhow!("seed range doesn't fit in usize"))?;
+        ranges.push(pair[0]..end);
+    }
+
+    for m in &p.mappings {
+        let ends = m
+            .pieces()
+            .iter()
+            .flat_map(|(src, _)| [src.start, src.end])
+            .collect::<Vec<_>>();
+
+        ranges = ranges
+            .into_iter()
+            .flat_map(|range| split(range, ends.iter().copied()))
+            .map(|piece| {
+                let start = map(m, piece.start);
+                start..start + piece.len()
+            })
+            .collect();
+    }
+
+    ranges
+        .iter()
+        .filter(|r| !r.is_empty())
+        .map(|r| r.start)
+        .min()
+        .ok_or_else(|| anyhow!("couldn't find min"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_INPUT;
+
+    #[test]
+    fn test_solve_part_1() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_1(&p).unwrap(), 35);
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_2(&p).unwrap(), 46);
+    }
+}
@@ -1,25 +1,40 @@
 use std::str::FromStr;
 
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
+use aoc_core::{Answer, Solution};
+use aoc_math::{checked_product, isqrt_u128};
+use aoc_parse::Span;
 
-#[derive(Debug, PartialEq)]
+pub mod reference;
+
+#[derive(Debug, PartialEq, Eq)]
 pub struct Problem {
-    records: Vec<(f64, f64)>,
+    records: Vec<(u64, u64)>,
 }
 
 impl FromStr for Problem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines().flat_map(|l| {
-            l.split_whitespace()
-                .skip(1)
-                .map(str::parse::<f64>)
-                .collect::<Result<Vec<_>, _>>()
-        });
-
-        let times = lines.next().ok_or_else(|| anyhow!("missing times"))?;
-        let distances = lines.next().ok_or_else(|| anyhow!("missing distances"))?;
+        let s = &aoc_parse::normalize(s);
+
+        let s = Span::new(s);
+        let mut lines = s.lines();
+
+        let times = lines.next().ok_or_else(|| s.error("missing times"))?;
+        let times = times.strip_prefix("Time:")?.integers::<u64>()?;
+
+        let distances = lines.next().ok_or_else(|| s.error("missing distances"))?;
+        let distances = distances.strip_prefix("Distance:")?.integers::<u64>()?;
+
+        if times.len() != distances.len() {
+            bail!(
+                "got {} race times but {} distance records",
+                times.len(),
+                distances.len()
+            );
+        }
+
         let records = times.into_iter().zip(distances).collect();
 
         Ok(Self { records })
@@ -43,43 +58,57 @@ impl FromStr for Problem {
 /// (-T + sqrt(T^2 - 4(-1)(-D))) / 2(-1) < t < (-T - sqrt(T^2 - 4(-1)(-D))) / 2(-1)
 /// (T - sqrt(Δ)) / 2 < t < (T + sqrt(Δ)) / 2
 #[must_use]
-#[allow(clippy::cast_sign_loss)]
-#[allow(clippy::cast_possible_truncation)]
-pub fn count_ways_to_win(race_time: f64, record_distance: f64) -> usize {
-    let t = race_time;
-    let d = record_distance;
-
-    let delta = t * t - 4.0 * d;
-    let left = (t - delta.sqrt()) / 2.0;
-    let right = (t + delta.sqrt()) / 2.0;
-    // Find first (/last) whole number strictly greater (/less) than found root(s), keeping in
-    // mind the case where it already falls at exact whole number (last example case graciously
-    // provided by the puzzle creator). Then, having that inclusive range, calculate number of
-    // whole numbers inside it (abs diff between them + 1).
-    let result = ((left + 1.0).floor() - (right - 1.0).ceil()).abs() + 1.0;
-
-    result.floor() as usize
+pub fn count_ways_to_win(race_time: u64, record_distance: u64) -> u64 {
+    let t = u128::from(race_time);
+    let d = u128::from(record_distance);
+
+    let wins = |held: u128| held * (t - held) > d;
+
+    // distance peaks when holding for half of the race
+    if !wins(t / 2) {
+        return 0;
+    }
+
+    // Rounded down integer root puts the estimate of the left root at most one step off, so
+    // nudge it to the first winning hold time. Winning times are symmetric around T / 2.
+    let mut left = (t - isqrt_u128(t * t - 4 * d)) / 2;
+
+    while !wins(left) {
+        left += 1;
+    }
+
+    while left > 0 && wins(left - 1) {
+        left -= 1;
+    }
+
+    let right = t - left;
+
+    // both bounds lie within 0..=race_time, which fits in u64
+    (right - left + 1) as u64
 }
 
-#[must_use]
-pub fn solve_part_1(p: &Problem) -> usize {
+/// # Errors
+///
+/// Returns error when the product of the numbers of ways to win overflows.
+pub fn solve_part_1(p: &Problem) -> Result<u64, anyhow::Error> {
     let Problem { records } = p;
 
-    records
+    let ways = records
         .iter()
-        .map(|&(race_time, record_distance)| count_ways_to_win(race_time, record_distance))
-        .product()
+        .map(|&(race_time, record_distance)| count_ways_to_win(race_time, record_distance));
+
+    checked_product(ways).ok_or_else(|| anyhow!("product of ways to win overflows u64"))
 }
 
 /// # Errors
 ///
 /// This function will return an error if either race times or distance records can't be merged
 /// into a single value.
-pub fn solve_part_2(p: &Problem) -> Result<usize, anyhow::Error> {
+pub fn solve_part_2(p: &Problem) -> Result<u64, anyhow::Error> {
     let Problem { records } = p;
     let (race_times, distance_records): (Vec<_>, Vec<_>) = records.iter().copied().unzip();
 
-    let merge_numbers = |v: &Vec<f64>| -> Result<f64, anyhow::Error> {
+    let merge_numbers = |v: &Vec<u64>| -> Result<u64, anyhow::Error> {
         let m = v
             .iter()
             .map(std::string::ToString::to_string)
@@ -96,36 +125,68 @@ pub fn solve_part_2(p: &Problem) -> Result<usize, anyhow::Error> {
     Ok(count_ways_to_win(race_time, record_distance))
 }
 
+const TEST_INPUT: &str = "\
+Time:      7  15   30
+Distance:  9  40  200";
+
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = 6;
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT];
+
+    fn part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_1(p)?.into())
+    }
+
+    fn part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_2(p)?.into())
+    }
+
+    fn reference_part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_1(p).into())
+    }
+
+    fn reference_part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_2(p)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = "\
-Time:      7  15   30
-Distance:  9  40  200";
-
     #[test]
     fn test_input_parsing() {
         let p: Problem = TEST_INPUT.parse().unwrap();
         assert_eq!(
             p,
             Problem {
-                records: vec![(7.0, 9.0), (15.0, 40.0), (30.0, 200.0)]
+                records: vec![(7, 9), (15, 40), (30, 200)]
             }
         );
+
+        let e = "Time: 7 15\nDistance: 9 4O".parse::<Problem>().unwrap_err();
+        assert!(e.to_string().starts_with("line 2, column 13: "));
+        assert!("Time: 7 15\nDistance: 9".parse::<Problem>().is_err());
     }
 
     #[test]
     fn test_count_ways_to_win() {
-        assert_eq!(count_ways_to_win(7.0, 9.0), 4);
-        assert_eq!(count_ways_to_win(15.0, 40.0), 8);
-        assert_eq!(count_ways_to_win(30.0, 200.0), 9);
+        assert_eq!(count_ways_to_win(7, 9), 4);
+        assert_eq!(count_ways_to_win(15, 40), 8);
+        assert_eq!(count_ways_to_win(30, 200), 9);
+        assert_eq!(count_ways_to_win(4, 4), 0);
+        assert_eq!(count_ways_to_win(5, 4), 2);
+        assert_eq!(count_ways_to_win(5, 6), 0);
     }
 
     #[test]
     fn test_solve_part_1() {
         let p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve_part_1(&p), 288);
+        assert_eq!(solve_part_1(&p).unwrap(), 288);
     }
 
     #[test]
@@ -133,4 +194,12 @@ Distance:  9  40  200";
         let p: Problem = TEST_INPUT.parse().unwrap();
         assert_eq!(solve_part_2(&p).unwrap(), 71503);
     }
+
+    #[test]
+    fn test_input_with_bom() {
+        let p: Problem = "\u{feff}Time:  7  15   30\r\nDistance:  9  40  200\r\n"
+            .parse()
+            .unwrap();
+        assert_eq!(p.records, vec![(7, 9), (15, 40), (30, 200)]);
+    }
 }
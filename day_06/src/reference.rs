@@ -0,0 +1,67 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
+
+use anyhow::Context;
+
+use crate::Problem;
+
+/// Tries holding the button for every possible time.
+fn count_ways_to_win(race_time: u64, record_distance: u64) -> u64 {
+    let mut ways = 0;
+
+    for held in 0..=race_time {
+        if u128::from(held) * u128::from(race_time - held) > u128::from(record_distance) {
+            ways += 1;
+        }
+    }
+
+    ways
+}
+
+#[must_use]
+pub fn solve_part_1(p: &Problem) -> u64 {
+    p.records
+        .iter()
+        .map(|&(race_time, record_distance)| count_ways_to_win(race_time, record_distance))
+        .product()
+}
+
+/// # Errors
+///
+/// Returns error when race times or distance records don't make a single number once their
+/// digits are put together.
+pub fn solve_part_2(p: &Problem) -> Result<u64, anyhow::Error> {
+    let mut race_time = String::new();
+    let mut record_distance = String::new();
+
+    for (time, distance) in &p.records {
+        race_time += &time.to_string();
+        record_distance += &distance.to_string();
+    }
+
+    let race_time = race_time
+        .parse()
+        .with_context(|| format!("couldn't merge race times into {race_time:?}"))?;
+    let record_distance = record_distance
+        .parse()
+        .with_context(|| format!("couldn't merge distance records into {record_distance:?}"))?;
+
+    Ok(count_ways_to_win(race_time, record_distance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_INPUT;
+
+    #[test]
+    fn test_solve_part_1() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_1(&p), 288);
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_2(&p).unwrap(), 71503);
+    }
+}
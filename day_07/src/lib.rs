@@ -1,6 +1,11 @@
 use std::{collections::HashMap, str::FromStr};
 
 use anyhow::{anyhow, bail};
+use aoc_core::{Answer, Part, Solution};
+use aoc_math::checked_sum;
+use aoc_parse::{FromSpan, ParseError, Span};
+
+pub mod reference;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum HandType {
@@ -121,15 +126,12 @@ struct Hand {
     cards: [Card; 5],
 }
 
-impl FromStr for Hand {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+impl FromSpan for Hand {
+    fn from_span(s: Span<'_>) -> Result<Self, ParseError> {
         let cards: [Card; 5] = s
-            .chars()
-            .map(std::convert::TryInto::try_into)
-            .collect::<Result<Vec<_>, _>>()?[..5]
-            .try_into()?;
+            .parse_chars()?
+            .try_into()
+            .map_err(|_| s.error("expected 5 cards"))?;
 
         Ok(Hand {
             cards,
@@ -147,14 +149,14 @@ impl FromStr for Problem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let games = s
+        let s = &aoc_parse::normalize(s);
+
+        let games = Span::new(s)
             .lines()
-            .map(|l| -> Result<(Hand, u64), Self::Err> {
-                let (hand, bet) = l
-                    .split_once(' ')
-                    .ok_or_else(|| anyhow!("couldn't split {:?} at space character", l))?;
+            .map(|l| -> Result<(Hand, u64), ParseError> {
+                let (hand, bet) = l.split_once(" ")?;
 
-                Ok((hand.parse()?, bet.parse()?))
+                Ok((Hand::from_span(hand)?, bet.parse()?))
             })
             .collect::<Result<_, _>>()?;
 
@@ -162,28 +164,54 @@ impl FromStr for Problem {
     }
 }
 
-pub fn solve(p: &mut Problem) -> u64 {
+/// # Errors
+///
+/// Returns error when the total winnings overflow.
+pub fn solve_part_2(p: &Problem) -> Result<u64, anyhow::Error> {
     let Problem { games } = p;
+    let mut games = games.clone();
 
     games.sort_by_key(|c| c.0);
 
-    (1u64..)
+    let winnings = (1u64..)
         .zip(games)
-        .map(|(rank, &mut (_, bid))| rank * bid)
-        .sum()
-}
+        .map(|(rank, (_, bid))| rank.checked_mul(bid))
+        .collect::<Option<Vec<_>>>();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    winnings
+        .and_then(checked_sum)
+        .ok_or_else(|| anyhow!("total winnings overflow u64"))
+}
 
-    const TEST_INPUT: &str = "\
+const TEST_INPUT: &str = "\
 32T3K 765
 T55J5 684
 KK677 28
 KTJJT 220
 QQQJA 483";
 
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = 7;
+    const IMPLEMENTED: &'static [Part] = &[Part::Two];
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT];
+
+    fn part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_2(p)?.into())
+    }
+
+    fn reference_part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_2(p).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_problem_parsing() {
         let p: Problem = TEST_INPUT.parse().unwrap();
@@ -238,15 +266,30 @@ QQQJA 483";
         );
     }
 
-    // #[test]
-    // fn test_solve_part_1() {
-    //     let mut p: Problem = TEST_INPUT.parse().unwrap();
-    //     assert_eq!(solve(&mut p), 6440);
-    // }
+    #[test]
+    fn test_parsing_errors() {
+        let e = "32T3K 765\nT5X5 684".parse::<Problem>().unwrap_err();
+        assert!(e
+            .to_string()
+            .starts_with("line 2, column 3: couldn't parse card from 'X'"));
+
+        let e = "32T3 765".parse::<Problem>().unwrap_err();
+        assert!(e
+            .to_string()
+            .starts_with("line 1, column 1: expected 5 cards"));
+        assert!("32T3K".parse::<Problem>().is_err());
+    }
 
     #[test]
     fn test_solve_part_2() {
-        let mut p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve(&mut p), 5905);
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_2(&p).unwrap(), 5905);
+    }
+
+    #[test]
+    fn test_trailing_whitespace() {
+        let p: Problem = TEST_INPUT.replace('\n', " \t\n").parse().unwrap();
+        assert_eq!(p.games.len(), 5);
+        assert_eq!(solve_part_2(&p).unwrap(), 5905);
     }
 }
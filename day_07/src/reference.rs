@@ -0,0 +1,108 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
+
+use crate::{Card, HandType, Problem};
+
+const NOT_JOKERS: [Card; 12] = [
+    Card::Two,
+    Card::Three,
+    Card::Four,
+    Card::Five,
+    Card::Six,
+    Card::Seven,
+    Card::Eight,
+    Card::Nine,
+    Card::Ten,
+    Card::Queen,
+    Card::King,
+    Card::Ace,
+];
+
+/// Type of a hand without jokers, told by how many times each card repeats.
+fn plain_type(cards: &[Card; 5]) -> HandType {
+    let mut counts = NOT_JOKERS
+        .iter()
+        .map(|card| cards.iter().filter(|&c| c == card).count())
+        .filter(|&count| count > 0)
+        .collect::<Vec<_>>();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+
+    match counts[..] {
+        [5] => HandType::FiveOfAKind,
+        [4, 1] => HandType::FourOfAKind,
+        [3, 2] => HandType::FullHouse,
+        [3, 1, 1] => HandType::ThreeOfAKind,
+        [2, 2, 1] => HandType::TwoPair,
+        [2, 1, 1, 1] => HandType::OnePair,
+        _ => HandType::HighCard,
+    }
+}
+
+/// Best type of all the hands jokers can turn into, trying every card for every joker.
+fn best_type(cards: [Card; 5]) -> HandType {
+    match cards.iter().position(|&c| c == Card::Joker) {
+        Some(i) => NOT_JOKERS
+            .iter()
+            .map(|&card| {
+                let mut cards = cards;
+                cards[i] = card;
+                best_type(cards)
+            })
+            .max()
+            .unwrap_or(HandType::HighCard),
+        None => plain_type(&cards),
+    }
+}
+
+#[must_use]
+pub fn solve_part_2(p: &Problem) -> u64 {
+    let mut games = p
+        .games
+        .iter()
+        .map(|(hand, bid)| (best_type(hand.cards), hand.cards, *bid))
+        .collect::<Vec<_>>();
+
+    // ties are broken card by card, with jokers being the weakest
+    games.sort();
+
+    let mut winnings = 0;
+
+    for (rank, (_, _, bid)) in (1..).zip(games) {
+        winnings += rank * bid;
+    }
+
+    winnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_INPUT;
+
+    #[test]
+    fn test_best_type() {
+        let hand = |s: &str| {
+            s.chars()
+                .map(|c| Card::try_from(c).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            best_type(hand("JJJJJ").try_into().unwrap()),
+            HandType::FiveOfAKind
+        );
+        assert_eq!(
+            best_type(hand("2233J").try_into().unwrap()),
+            HandType::FullHouse
+        );
+        assert_eq!(
+            best_type(hand("2345J").try_into().unwrap()),
+            HandType::OnePair
+        );
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_2(&p), 5905);
+    }
+}
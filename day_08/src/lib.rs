@@ -1,6 +1,12 @@
 use std::{collections::HashMap, str::FromStr};
 
 use anyhow::{anyhow, bail};
+use aoc_core::{trace, Answer, Layout, Solution};
+use aoc_graph::reachable;
+use aoc_math::lcm_all;
+use aoc_parse::{ParseError, Span};
+
+pub mod reference;
 
 #[derive(Debug, PartialEq, Eq)]
 enum Instruction {
@@ -20,7 +26,7 @@ impl TryFrom<char> for Instruction {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Problem {
     instructions: Vec<Instruction>,
     map: HashMap<String, (String, String)>,
@@ -30,25 +36,31 @@ impl FromStr for Problem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (instructions, map) = s
-            .split_once("\n\n")
-            .ok_or_else(|| anyhow!("couldn't split input"))?;
+        let s = &aoc_parse::normalize(s);
 
-        let instructions = instructions
-            .chars()
-            .map(std::convert::TryInto::try_into)
-            .collect::<Result<_, _>>()?;
+        let s = Span::new(s);
+        let mut sections = s.sections();
+
+        let (Some(instructions), Some(map), None) =
+            (sections.next(), sections.next(), sections.next())
+        else {
+            bail!(s.error("expected instructions and a map separated by a blank line"));
+        };
+
+        let instructions = instructions.parse_chars()?;
 
         let map = map
             .lines()
-            .map(|l| -> Result<_, anyhow::Error> {
-                let (key, pair) = l
-                    .split_once(" = ")
-                    .ok_or_else(|| anyhow!("couldn't split {:?} at ' = '", l))?;
+            .map(|l| -> Result<_, ParseError> {
+                let (key, pair) = l.split_once(" = ")?;
+                let (left, right) = pair
+                    .strip_prefix("(")?
+                    .strip_suffix(")")?
+                    .split_once(", ")?;
 
                 Ok((
-                    key.to_string(),
-                    (pair[1..4].to_string(), pair[6..9].to_string()),
+                    key.as_str().to_string(),
+                    (left.as_str().to_string(), right.as_str().to_string()),
                 ))
             })
             .collect::<Result<_, _>>()?;
@@ -57,17 +69,65 @@ impl FromStr for Problem {
     }
 }
 
-#[must_use]
-pub fn solve_part_1(p: &Problem) -> usize {
+// Following the instructions can only ever end if some end node is reachable at all. This also
+// makes sure the walk never steps onto an unknown node.
+fn check_reachable(
+    map: &HashMap<String, (String, String)>,
+    start: &str,
+    is_end: impl Fn(&str) -> bool,
+) -> Result<(), anyhow::Error> {
+    if !map.contains_key(start) {
+        bail!("unknown node {:?}", start);
+    }
+
+    let nodes = reachable(start, |&node| {
+        map.get(node)
+            .map(|(left, right)| [left.as_str(), right.as_str()])
+            .into_iter()
+            .flatten()
+    });
+
+    if let Some(node) = nodes.iter().find(|&&n| !map.contains_key(n)) {
+        bail!("unknown node {:?}", node);
+    }
+
+    if !nodes.iter().any(|&n| is_end(n)) {
+        bail!("no end node can be reached from {:?}", start);
+    }
+
+    Ok(())
+}
+
+// Walking is a deterministic state machine over pairs of a node and a position in the
+// instructions, so a walk taking more steps than there are such pairs goes around in circles.
+fn steps_to_end(
+    p: &Problem,
+    start: &str,
+    is_end: impl Fn(&str) -> bool,
+) -> Result<usize, anyhow::Error> {
     let Problem { instructions, map } = p;
-    let mut current_node = "AAA";
+    let mut current_node = start;
+
+    check_reachable(map, start, &is_end)?;
 
-    for (i, instr) in instructions.iter().cycle().enumerate() {
-        if current_node == "ZZZ" {
-            return i;
+    let states = map.len() * instructions.len();
+    let mut path = trace::enabled().then(Vec::new);
+
+    for (i, instr) in instructions.iter().cycle().enumerate().take(states + 1) {
+        if let Some(path) = &mut path {
+            path.push(current_node);
+        }
+
+        if is_end(current_node) {
+            if let Some(path) = path {
+                trace!("{} in {} steps", path.join(" -> "), i);
+            }
+            return Ok(i);
         }
 
-        let paths = &map[current_node];
+        let paths = map
+            .get(current_node)
+            .ok_or_else(|| anyhow!("unknown node {:?}", current_node))?;
 
         match instr {
             Instruction::Left => current_node = &paths.0,
@@ -75,55 +135,45 @@ pub fn solve_part_1(p: &Problem) -> usize {
         }
     }
 
-    unreachable!()
+    bail!(
+        "following the instructions never leads from {:?} to an end node",
+        start
+    )
 }
 
-fn gcd(a: usize, b: usize) -> usize {
-    if b == 0 {
-        return a;
-    }
-
-    gcd(b, a.rem_euclid(b))
+/// # Errors
+///
+/// Returns error when following the instructions doesn't lead from "AAA" to "ZZZ".
+pub fn solve_part_1(p: &Problem) -> Result<usize, anyhow::Error> {
+    steps_to_end(p, "AAA", |n| n == "ZZZ")
 }
 
-fn lcm(a: usize, b: usize) -> usize {
-    a * (b / gcd(a, b))
-}
-
-#[must_use]
-pub fn solve_part_2(p: &Problem) -> Option<usize> {
-    let Problem { instructions, map } = p;
-    let starting_nodes = map.keys().filter(|k| k.ends_with('A'));
-
-    let steps_to_reach = starting_nodes
-        .map(|n| {
-            let mut current_node = n;
-
-            for (i, instr) in instructions.iter().cycle().enumerate() {
-                if current_node.ends_with('Z') {
-                    return i;
-                }
-
-                let paths = &map[current_node];
+/// # Errors
+///
+/// Returns error when there are no starting nodes, some of them never reach an end node, or the
+/// number of steps overflows.
+pub fn solve_part_2(p: &Problem) -> Result<u64, anyhow::Error> {
+    // sorted, so notes come in the same order on every run
+    let mut starts = p
+        .map
+        .keys()
+        .filter(|k| k.ends_with('A'))
+        .collect::<Vec<_>>();
+    starts.sort_unstable();
 
-                match instr {
-                    Instruction::Left => current_node = &paths.0,
-                    Instruction::Right => current_node = &paths.1,
-                }
-            }
+    let steps_to_reach = starts
+        .into_iter()
+        .map(|n| Ok(steps_to_end(p, n, |n| n.ends_with('Z'))? as u64))
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
 
-            unreachable!()
-        })
-        .collect::<Vec<_>>();
+    if steps_to_reach.is_empty() {
+        bail!("no starting nodes");
+    }
 
-    steps_to_reach.into_iter().reduce(lcm)
+    lcm_all(&steps_to_reach).ok_or_else(|| anyhow!("number of steps overflows u64"))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "\
+const TEST_INPUT: &str = "\
 RL
 
 AAA = (BBB, CCC)
@@ -134,14 +184,7 @@ EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)";
 
-    const TEST_INPUT_2: &str = "\
-LLR
-
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)";
-
-    const TEST_INPUT_3: &str = "\
+const TEST_INPUT_3: &str = "\
 LR
 
 11A = (11B, XXX)
@@ -153,35 +196,117 @@ LR
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
 
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = 8;
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT_3];
+    const LAYOUT: Layout = Layout::Sections { header: 0 };
+
+    fn part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_1(p)?.into())
+    }
+
+    fn part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_2(p)?.into())
+    }
+
+    fn reference_part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_1(p)?.into())
+    }
+
+    fn reference_part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_2(p)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT_2: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
     #[test]
     fn test_input_parsing() {
         let p: Problem = TEST_INPUT.parse().unwrap();
         assert_eq!(p.instructions, vec![Instruction::Right, Instruction::Left]);
         assert_eq!(p.map.len(), 7);
         assert_eq!(p.map["AAA"], ("BBB".to_string(), "CCC".to_string()));
+
+        let e = "LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA ZZZ)"
+            .parse::<Problem>()
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!((e.line, e.column), (4, 8));
+        assert_eq!(e.message, "expected \", \"");
+        assert!("LR\n\nAAA = BBB, ZZZ".parse::<Problem>().is_err());
+        assert!("LRX\n\nAAA = (AAA, AAA)".parse::<Problem>().is_err());
     }
 
     #[test]
     fn test_solve_part_1() {
         let p1: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve_part_1(&p1), 2);
+        assert_eq!(solve_part_1(&p1).unwrap(), 2);
         let p2: Problem = TEST_INPUT_2.parse().unwrap();
-        assert_eq!(solve_part_1(&p2), 6);
+        assert_eq!(solve_part_1(&p2).unwrap(), 6);
     }
 
     #[test]
-    fn test_gcd() {
-        assert_eq!(gcd(48, 18), 6);
+    fn test_explain() {
+        let p: Problem = TEST_INPUT_2.parse().unwrap();
+        let (_, notes) = trace::collect(|| solve_part_1(&p));
+        assert_eq!(
+            notes,
+            ["AAA -> BBB -> AAA -> BBB -> AAA -> BBB -> ZZZ in 6 steps"]
+        );
+
+        let p: Problem = TEST_INPUT_3.parse().unwrap();
+        let (_, notes) = trace::collect(|| solve_part_2(&p));
+        assert_eq!(
+            notes,
+            [
+                "11A -> 11B -> 11Z in 2 steps",
+                "22A -> 22B -> 22C -> 22Z in 3 steps"
+            ]
+        );
     }
 
     #[test]
-    fn test_lcm() {
-        assert_eq!(lcm(21, 6), 42);
+    fn test_unreachable_end() {
+        let p: Problem = "L\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, BBB)".parse().unwrap();
+        assert!(solve_part_1(&p).is_err());
+
+        let p: Problem = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, BBB)\nZZZ = (ZZZ, ZZZ)"
+            .parse()
+            .unwrap();
+        assert!(solve_part_1(&p).is_err());
+
+        // ZZZ can be reached, just not by only ever going left
+        let p: Problem = "L\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)"
+            .parse()
+            .unwrap();
+        assert!(solve_part_1(&p).is_err());
     }
 
     #[test]
     fn test_solve_part_2() {
         let p: Problem = TEST_INPUT_3.parse().unwrap();
-        assert_eq!(solve_part_2(&p), Some(6));
+        assert_eq!(solve_part_2(&p).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_input_with_bom() {
+        let input = format!("\u{feff}{}\r\n", TEST_INPUT.replace('\n', "\r\n"));
+        let p: Problem = input.parse().unwrap();
+        assert_eq!(p.instructions, vec![Instruction::Right, Instruction::Left]);
+        assert_eq!(p.map["AAA"], ("BBB".to_string(), "CCC".to_string()));
     }
 }
@@ -0,0 +1,90 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
+
+use anyhow::{anyhow, bail};
+
+use crate::{Instruction, Problem};
+
+fn step<'a>(p: &'a Problem, node: &str, i: usize) -> Result<&'a str, anyhow::Error> {
+    let (left, right) = p
+        .map
+        .get(node)
+        .ok_or_else(|| anyhow!("unknown node {:?}", node))?;
+
+    Ok(match p.instructions[i % p.instructions.len()] {
+        Instruction::Left => left,
+        Instruction::Right => right,
+    })
+}
+
+/// Moves all walkers at once, one instruction at a time, until every one of them stands on an end
+/// node. Gives up once the walkers have gone through more steps than they have states together.
+fn walk<'a>(
+    p: &'a Problem,
+    mut nodes: Vec<&'a str>,
+    is_end: impl Fn(&str) -> bool,
+) -> Result<u64, anyhow::Error> {
+    let states = (p.map.len() * p.instructions.len()) as u64;
+    let limit =
+        u32::try_from(nodes.len()).map_or(u64::MAX, |walkers| states.saturating_pow(walkers));
+
+    for steps in 0..=limit {
+        if nodes.iter().all(|&n| is_end(n)) {
+            return Ok(steps);
+        }
+
+        let i = usize::try_from(steps)?;
+
+        for node in &mut nodes {
+            *node = step(p, node, i)?;
+        }
+    }
+
+    bail!("following the instructions never leads to end nodes only")
+}
+
+/// # Errors
+///
+/// Returns error when following the instructions doesn't lead from "AAA" to "ZZZ".
+pub fn solve_part_1(p: &Problem) -> Result<u64, anyhow::Error> {
+    walk(p, vec!["AAA"], |n| n == "ZZZ")
+}
+
+/// # Errors
+///
+/// Returns error when there are no starting nodes or the ghosts never all stand on end nodes at
+/// the same time.
+pub fn solve_part_2(p: &Problem) -> Result<u64, anyhow::Error> {
+    let starts = p
+        .map
+        .keys()
+        .filter(|k| k.ends_with('A'))
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+
+    if starts.is_empty() {
+        bail!("no starting nodes");
+    }
+
+    walk(p, starts, |n| n.ends_with('Z'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TEST_INPUT, TEST_INPUT_3};
+
+    #[test]
+    fn test_solve_part_1() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_1(&p).unwrap(), 2);
+
+        let p: Problem = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)".parse().unwrap();
+        assert!(solve_part_1(&p).is_err());
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p: Problem = TEST_INPUT_3.parse().unwrap();
+        assert_eq!(solve_part_2(&p).unwrap(), 6);
+    }
+}
@@ -1,4 +1,11 @@
-use std::str::FromStr;
+use std::{io::BufRead, str::FromStr};
+
+use anyhow::{anyhow, bail};
+use aoc_core::{stream::try_fold_lines, Answer, Part, Solution};
+use aoc_math::checked_sum;
+use aoc_parse::Span;
+
+pub mod reference;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Problem {
@@ -9,46 +16,50 @@ impl FromStr for Problem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let sequences = s
+        let s = &aoc_parse::normalize(s);
+
+        let sequences = Span::new(s)
             .lines()
-            .flat_map(|l| {
-                l.split_ascii_whitespace()
-                    .map(str::parse)
-                    .collect::<Result<_, _>>()
-            })
-            .collect();
+            .map(|l| l.integers())
+            .collect::<Result<_, _>>()?;
 
         Ok(Problem { sequences })
     }
 }
 
-fn solve(seq: &[i64]) -> i64 {
-    let a = seq.iter().rev().copied().collect::<Vec<_>>();
-
-    let mut diff_seqs: Vec<Vec<i64>> = vec![a];
-    let mut ptrs: Vec<usize> = vec![0];
-
-    while diff_seqs
-        .last()
-        .and_then(|s| s.first())
-        .is_some_and(|&n| n != 0)
-    {
-        diff_seqs.push(vec![]);
+// Next value is the sum of last values of all rows of differences. Looking at last values alone
+// isn't enough to tell that a row settled, a row ending in zero can still go on changing.
+fn solve(seq: &[i64]) -> Result<i64, anyhow::Error> {
+    if seq.is_empty() {
+        bail!("empty sequence");
+    }
 
-        for (seq_i, ptr) in ptrs.iter_mut().enumerate() {
-            let new = diff_seqs[seq_i][*ptr] - diff_seqs[seq_i][*ptr + 1];
-            diff_seqs[seq_i + 1].push(new);
+    let mut row = seq.to_vec();
+    let mut next = 0i64;
 
-            *ptr += 1;
+    while row.iter().any(|&n| n != 0) {
+        if row.len() == 1 {
+            bail!("differences of {:?} don't settle at zero", seq);
         }
 
-        ptrs.push(0);
+        next = next
+            .checked_add(row[row.len() - 1])
+            .ok_or_else(|| overflow(seq))?;
+        row = row
+            .windows(2)
+            .map(|w| w[1].checked_sub(w[0]))
+            .collect::<Option<_>>()
+            .ok_or_else(|| overflow(seq))?;
     }
 
-    diff_seqs.into_iter().map(|s| s[0]).sum()
+    Ok(next)
 }
 
-fn solve2(seq: &[i64]) -> i64 {
+fn solve2(seq: &[i64]) -> Result<i64, anyhow::Error> {
+    if seq.is_empty() {
+        bail!("empty sequence");
+    }
+
     let mut diff_seqs: Vec<Vec<i64>> = vec![seq.to_vec()];
     let mut ptrs: Vec<usize> = vec![0];
 
@@ -62,7 +73,9 @@ fn solve2(seq: &[i64]) -> i64 {
             if *ptr + 1 == diff_seqs[seq_i].len() {
                 break 'outer;
             }
-            let new = diff_seqs[seq_i][*ptr + 1] - diff_seqs[seq_i][*ptr];
+            let new = diff_seqs[seq_i][*ptr + 1]
+                .checked_sub(diff_seqs[seq_i][*ptr])
+                .ok_or_else(|| overflow(seq))?;
             diff_seqs[seq_i + 1].push(new);
 
             *ptr += 1;
@@ -75,30 +88,98 @@ fn solve2(seq: &[i64]) -> i64 {
         .iter()
         .rev()
         .filter_map(|v| v.first())
-        .fold(0, |acc, s| -acc + s)
+        .try_fold(0i64, |acc, s| s.checked_sub(acc))
+        .ok_or_else(|| overflow(seq))
+}
+
+fn overflow(seq: &[i64]) -> anyhow::Error {
+    anyhow!("extrapolating {:?} overflows i64", seq)
 }
 
-#[must_use]
-pub fn solve_part_1(p: &Problem) -> i64 {
+/// # Errors
+///
+/// Returns error when some sequence can't be extrapolated or the sum overflows.
+pub fn solve_part_1(p: &Problem) -> Result<i64, anyhow::Error> {
     let Problem { sequences } = p;
-    sequences.iter().map(|seq| solve(seq)).sum()
+    let values = sequences
+        .iter()
+        .map(|seq| solve(seq))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    checked_sum(values).ok_or_else(|| anyhow!("sum of extrapolated values overflows i64"))
 }
 
-#[must_use]
-pub fn solve_part_2(p: &Problem) -> i64 {
+/// # Errors
+///
+/// Returns error when some sequence can't be extrapolated or the sum overflows.
+pub fn solve_part_2(p: &Problem) -> Result<i64, anyhow::Error> {
     let Problem { sequences } = p;
-    sequences.iter().map(|seq| solve2(seq)).sum()
+    let values = sequences
+        .iter()
+        .map(|seq| solve2(seq))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    checked_sum(values).ok_or_else(|| anyhow!("sum of extrapolated values overflows i64"))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Either part, reading the input a sequence at a time instead of parsing it whole.
+///
+/// # Errors
+///
+/// Returns error when the input can't be read or a sequence can't be parsed, otherwise the same
+/// errors as [`solve_part_1`] and [`solve_part_2`].
+pub fn stream(input: &mut dyn BufRead, part: Part) -> Result<i64, anyhow::Error> {
+    let extrapolate = match part {
+        Part::One => solve,
+        Part::Two => solve2,
+    };
+
+    try_fold_lines(input, 0i64, |sum, line| {
+        let seq = Span::new(line).integers()?;
 
-    const TEST_INPUT: &str = "\
+        sum.checked_add(extrapolate(&seq)?)
+            .ok_or_else(|| anyhow!("sum of extrapolated values overflows i64"))
+    })
+}
+
+const TEST_INPUT: &str = "\
 0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45";
 
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = 9;
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT];
+
+    fn part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_1(p)?.into())
+    }
+
+    fn part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_2(p)?.into())
+    }
+
+    fn reference_part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_1(p)?.into())
+    }
+
+    fn reference_part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_2(p)?.into())
+    }
+
+    fn stream(input: &mut dyn BufRead, part: Part) -> Option<Result<Answer, anyhow::Error>> {
+        Some(stream(input, part).map(Answer::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_input_parsing() {
         let p: Problem = TEST_INPUT.parse().unwrap();
@@ -112,30 +193,53 @@ mod tests {
                 ]
             }
         );
+
+        let e = "0 3 6\n1 3 x 10".parse::<Problem>().unwrap_err();
+        assert!(e.to_string().starts_with("line 2, column 5: "));
     }
 
     #[test]
     fn test_solve() {
-        assert_eq!(solve(&[0, 3, 6, 9, 12, 15]), 18);
+        assert_eq!(solve(&[0, 3, 6, 9, 12, 15]).unwrap(), 18);
+        // differences end in zero before they settle
+        assert_eq!(solve(&[0, -2, -3, -3]).unwrap(), -2);
+        assert_eq!(solve(&[4, 4, 4]).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_unsettled_sequences() {
+        assert!(solve(&[]).is_err());
+        assert!(solve2(&[]).is_err());
+        assert!(solve(&[1, 2, 4, 8]).is_err());
+        assert!(solve(&[0, i64::MAX, i64::MIN]).is_err());
     }
 
     #[test]
     fn test_solve_part_1() {
         let p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve_part_1(&p), 114);
+        assert_eq!(solve_part_1(&p).unwrap(), 114);
+    }
+
+    #[test]
+    fn test_stream() {
+        assert_eq!(stream(&mut TEST_INPUT.as_bytes(), Part::One).unwrap(), 114);
+        assert_eq!(stream(&mut TEST_INPUT.as_bytes(), Part::Two).unwrap(), 2);
+
+        let e = stream(&mut "0 3 6\n1 3 x 10".as_bytes(), Part::One).unwrap_err();
+        assert!(e.to_string().starts_with("line 2, column 5: "));
     }
 
     #[test]
     fn test_solve2() {
-        assert_eq!(solve2(&[0, 3, 6, 9, 12, 15]), -3);
-        assert_eq!(solve2(&[1, 3, 6, 10, 15, 21]), 0);
-        assert_eq!(solve2(&[10, 13, 16, 21, 30, 45]), 5);
+        assert_eq!(solve2(&[0, 3, 6, 9, 12, 15]).unwrap(), -3);
+        assert_eq!(solve2(&[1, 3, 6, 10, 15, 21]).unwrap(), 0);
+        assert_eq!(solve2(&[10, 13, 16, 21, 30, 45]).unwrap(), 5);
     }
 
     #[test]
     fn test_solve_part_2() {
         let p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve_part_2(&p), 2);
+        assert_eq!(solve_part_2(&p).unwrap(), 2);
     }
 
     #[test]
@@ -144,8 +248,15 @@ mod tests {
             solve2(&[
                 3, 10, 27, 68, 169, 402, 899, 1895, 3816, 7471, 14456, 27943, 54110, 104_575,
                 200_340, 377_950, 698_857, 1_263_392, 2_231_345, 3_852_006, 6_507_719
-            ]),
+            ])
+            .unwrap(),
             4
         );
     }
+
+    #[test]
+    fn test_input_with_bom() {
+        let p: Problem = "\u{feff}0 -3 -6\r\n10 13 16\r\n".parse().unwrap();
+        assert_eq!(p.sequences, vec![vec![0, -3, -6], vec![10, 13, 16]]);
+    }
 }
@@ -0,0 +1,97 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
+
+use anyhow::{anyhow, bail};
+
+use crate::Problem;
+
+/// The sequence followed by rows of differences between neighbours, down to the first one of
+/// nothing but zeros.
+fn differences(seq: &[i64]) -> Result<Vec<Vec<i128>>, anyhow::Error> {
+    let mut rows = vec![seq.iter().map(|&n| i128::from(n)).collect::<Vec<_>>()];
+
+    loop {
+        let last = &rows[rows.len() - 1];
+
+        if last.is_empty() {
+            bail!("differences of {:?} don't settle at zero", seq);
+        }
+
+        if last.iter().all(|&n| n == 0) {
+            return Ok(rows);
+        }
+
+        rows.push(last.windows(2).map(|w| w[1] - w[0]).collect());
+    }
+}
+
+fn to_answer(values: Vec<i128>) -> Result<i64, anyhow::Error> {
+    let sum = values.into_iter().sum::<i128>();
+
+    i64::try_from(sum).map_err(|_| anyhow!("sum of extrapolated values overflows i64"))
+}
+
+/// # Errors
+///
+/// Returns error when differences of some sequence don't settle at zero.
+pub fn solve_part_1(p: &Problem) -> Result<i64, anyhow::Error> {
+    let mut values = vec![];
+
+    for seq in &p.sequences {
+        let mut next = 0;
+
+        for row in differences(seq)?.iter().rev() {
+            next += row[row.len() - 1];
+        }
+
+        values.push(next);
+    }
+
+    to_answer(values)
+}
+
+/// # Errors
+///
+/// Returns error when differences of some sequence don't settle at zero.
+pub fn solve_part_2(p: &Problem) -> Result<i64, anyhow::Error> {
+    let mut values = vec![];
+
+    for seq in &p.sequences {
+        let mut previous = 0;
+
+        for row in differences(seq)?.iter().rev() {
+            previous = row[0] - previous;
+        }
+
+        values.push(previous);
+    }
+
+    to_answer(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_INPUT;
+
+    #[test]
+    fn test_differences() {
+        assert_eq!(
+            differences(&[1, 3, 6, 10]).unwrap(),
+            vec![vec![1, 3, 6, 10], vec![2, 3, 4], vec![1, 1], vec![0]]
+        );
+        assert!(differences(&[1, 2, 4, 8]).is_err());
+        assert!(differences(&[]).is_err());
+    }
+
+    #[test]
+    fn test_solve_part_1() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_1(&p).unwrap(), 114);
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_2(&p).unwrap(), 2);
+    }
+}
@@ -1,6 +1,11 @@
 use std::str::FromStr;
 
 use anyhow::{anyhow, bail};
+use aoc_core::{Answer, Layout, Solution};
+use aoc_geometry::{interior_points, Direction, Point};
+use aoc_grid::Grid;
+
+pub mod reference;
 
 #[derive(Debug, PartialEq, Eq)]
 enum Tile {
@@ -14,27 +19,27 @@ enum Tile {
     SouthEast,
 }
 
-impl TryFrom<u8> for Tile {
+impl TryFrom<char> for Tile {
     type Error = anyhow::Error;
 
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            b'|' => Ok(Self::NorthSouth),
-            b'-' => Ok(Self::EastWest),
-            b'L' => Ok(Self::NorthEast),
-            b'J' => Ok(Self::NorthWest),
-            b'7' => Ok(Self::SouthWest),
-            b'F' => Ok(Self::SouthEast),
-            b'.' => Ok(Self::Ground),
-            b'S' => Ok(Self::Start),
+            '|' => Ok(Self::NorthSouth),
+            '-' => Ok(Self::EastWest),
+            'L' => Ok(Self::NorthEast),
+            'J' => Ok(Self::NorthWest),
+            '7' => Ok(Self::SouthWest),
+            'F' => Ok(Self::SouthEast),
+            '.' => Ok(Self::Ground),
+            'S' => Ok(Self::Start),
             c => bail!("couldn't parse Tile from {:?}", c),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Problem {
-    map: Vec<Vec<Tile>>,
+    map: Grid<Tile>,
     start_pos: (usize, usize),
 }
 
@@ -42,218 +47,152 @@ impl FromStr for Problem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let map = s
-            .lines()
-            .flat_map(|l| {
-                l.bytes()
-                    .map(std::convert::TryInto::try_into)
-                    .collect::<Result<_, _>>()
-            })
-            .collect::<Vec<Vec<Tile>>>();
+        let s = &aoc_parse::normalize(s);
+
+        let map: Grid<Tile> = s.parse()?;
 
         let start_pos = map
-            .iter()
-            .enumerate()
-            .find_map(|(i, v)| v.iter().position(|t| *t == Tile::Start).map(|j| (i, j)))
+            .position(|t| *t == Tile::Start)
             .ok_or_else(|| anyhow!("couldn't find starting position"))?;
 
         Ok(Self { map, start_pos })
     }
 }
 
-// max_x, max_y are typically `arr.len() - 1`, `arr[0].len() - 1`
-fn neighbour_indices_8dir(
-    (x, y): (usize, usize),
-    (max_x, max_y): (usize, usize),
-) -> Vec<(usize, usize)> {
-    match (
-        x.checked_sub(1),
-        (x < max_x).then_some(x + 1),
-        y.checked_sub(1),
-        (y < max_y).then_some(y + 1),
-    ) {
-        (None, Some(xp), None, Some(yp)) => vec![(x, yp), (xp, y), (xp, yp)],
-        (None, Some(xp), Some(ym), None) => vec![(x, ym), (xp, y), (xp, ym)],
-        (None, Some(xp), Some(ym), Some(yp)) => vec![(x, ym), (x, yp), (xp, y), (xp, ym), (xp, yp)],
-        (Some(xm), None, None, Some(yp)) => vec![(x, yp), (xm, y), (xm, yp)],
-        (Some(xm), None, Some(ym), None) => vec![(x, ym), (xm, y), (xm, ym)],
-        (Some(xm), None, Some(ym), Some(yp)) => vec![(x, ym), (x, yp), (xm, y), (xm, ym), (xm, yp)],
-        (Some(xm), Some(xp), None, Some(yp)) => vec![(x, yp), (xm, y), (xp, y), (xm, yp), (xp, yp)],
-        (Some(xm), Some(xp), Some(ym), None) => vec![(x, ym), (xm, y), (xp, y), (xm, ym), (xp, ym)],
-        (Some(xm), Some(xp), Some(ym), Some(yp)) => vec![
-            (x, ym),
-            (x, yp),
-            (xm, y),
-            (xp, y),
-            (xm, ym),
-            (xm, yp),
-            (xp, ym),
-            (xp, yp),
-        ],
-        // all unreachable unless we are on a 1-wide/1-tall map (spoiler: we are not)
-        (None | Some(_), None | Some(_), None, None)
-        | (None, None, None | Some(_), None | Some(_)) => unreachable!(),
+impl Tile {
+    /// Directions the pipe leads to. Shape of the start tile is unknown, so it has none.
+    fn connections(&self) -> Option<[Direction; 2]> {
+        match self {
+            Tile::NorthSouth => Some([Direction::North, Direction::South]),
+            Tile::EastWest => Some([Direction::East, Direction::West]),
+            Tile::NorthEast => Some([Direction::North, Direction::East]),
+            Tile::NorthWest => Some([Direction::North, Direction::West]),
+            Tile::SouthWest => Some([Direction::South, Direction::West]),
+            Tile::SouthEast => Some([Direction::South, Direction::East]),
+            Tile::Ground | Tile::Start => None,
+        }
     }
 }
 
-// max_x, max_y are typically `arr.len() - 1`, `arr[0].len() - 1`
-fn neighbour_indices_4dir(
-    (x, y): (usize, usize),
-    (max_x, max_y): (usize, usize),
-) -> Vec<(usize, usize)> {
-    match (
-        x.checked_sub(1),
-        (x < max_x).then_some(x + 1),
-        y.checked_sub(1),
-        (y < max_y).then_some(y + 1),
-    ) {
-        (None, Some(xp), None, Some(yp)) => vec![(x, yp), (xp, y)],
-        (None, Some(xp), Some(ym), None) => vec![(x, ym), (xp, y)],
-        (None, Some(xp), Some(ym), Some(yp)) => vec![(x, ym), (x, yp), (xp, y)],
-        (Some(xm), None, None, Some(yp)) => vec![(x, yp), (xm, y)],
-        (Some(xm), None, Some(ym), None) => vec![(x, ym), (xm, y)],
-        (Some(xm), None, Some(ym), Some(yp)) => vec![(x, ym), (x, yp), (xm, y)],
-        (Some(xm), Some(xp), None, Some(yp)) => vec![(x, yp), (xm, y), (xp, y)],
-        (Some(xm), Some(xp), Some(ym), None) => vec![(x, ym), (xm, y), (xp, y)],
-        (Some(xm), Some(xp), Some(ym), Some(yp)) => vec![(x, ym), (x, yp), (xm, y), (xp, y)],
-        // all unreachable unless we are on a 1-wide/1-tall map (spoiler: we are not)
-        (None | Some(_), None | Some(_), None, None)
-        | (None, None, None | Some(_), None | Some(_)) => unreachable!(),
-    }
-}
+// Direction in which we leave `tile` after entering it while heading in `heading`, if the pipe
+// connects to where we came from.
+fn get_exit_direction(heading: Direction, tile: &Tile) -> Option<Direction> {
+    let [a, b] = tile.connections()?;
+    let entry = heading.opposite();
 
-fn get_exit_pos(
-    entry_pos: (usize, usize),
-    tile_pos: (usize, usize),
-    tile: &Tile,
-) -> (usize, usize) {
-    match tile {
-        Tile::NorthSouth => {
-            if entry_pos.0 + 1 == tile_pos.0 {
-                (tile_pos.0 + 1, tile_pos.1)
-            } else {
-                (tile_pos.0 - 1, tile_pos.1)
-            }
-        }
-        Tile::EastWest => {
-            if entry_pos.1 + 1 == tile_pos.1 {
-                (tile_pos.0, tile_pos.1 + 1)
-            } else {
-                (tile_pos.0, tile_pos.1 - 1)
-            }
-        }
-        Tile::NorthEast => {
-            if entry_pos.0 == tile_pos.0 {
-                (tile_pos.0 - 1, tile_pos.1)
-            } else {
-                (tile_pos.0, tile_pos.1 + 1)
-            }
-        }
-        Tile::NorthWest => {
-            if entry_pos.0 == tile_pos.0 {
-                (tile_pos.0 - 1, tile_pos.1)
-            } else {
-                (tile_pos.0, tile_pos.1 - 1)
-            }
-        }
-        Tile::SouthWest => {
-            if entry_pos.0 == tile_pos.0 {
-                (tile_pos.0 + 1, tile_pos.1)
-            } else {
-                (tile_pos.0, tile_pos.1 - 1)
-            }
-        }
-        Tile::SouthEast => {
-            if entry_pos.0 == tile_pos.0 {
-                (tile_pos.0 + 1, tile_pos.1)
-            } else {
-                (tile_pos.0, tile_pos.1 + 1)
-            }
-        }
-        // we never enter ground tiles, and we stop before we loop back to start tile
-        Tile::Ground | Tile::Start => unreachable!(),
+    if a == entry {
+        Some(b)
+    } else if b == entry {
+        Some(a)
+    } else {
+        None
     }
 }
 
-fn get_start_pipes(
-    map: &Vec<Vec<Tile>>,
-    start_pos: (usize, usize),
-) -> ((usize, usize), (usize, usize)) {
-    let start_pipe_candidates =
-        neighbour_indices_4dir(start_pos, (map.len() - 1, map[0].len() - 1));
+fn tile_at(map: &Grid<Tile>, pos: Point) -> Option<&Tile> {
+    map.get(pos.to_pos()?)
+}
 
-    let (x, y) = start_pos;
+// Positions of all tiles of the loop, in order, starting with the start tile.
+fn find_loop(map: &Grid<Tile>, start_pos: (usize, usize)) -> Result<Vec<Point>, anyhow::Error> {
+    let start = Point::from_pos(start_pos);
 
-    let start_pipes = start_pipe_candidates
+    let mut heading = Direction::ALL
         .into_iter()
-        .filter_map(|(nx, ny)| match map[nx][ny] {
-            Tile::NorthSouth | Tile::NorthEast | Tile::NorthWest if nx == x + 1 => Some((nx, ny)),
-            Tile::NorthSouth | Tile::SouthEast | Tile::SouthWest
-                if x.checked_sub(1).is_some_and(|xm| nx == xm) =>
-            {
-                Some((nx, ny))
-            }
-            Tile::EastWest | Tile::NorthWest | Tile::SouthWest if ny == y + 1 => Some((nx, ny)),
-            Tile::EastWest | Tile::NorthEast | Tile::SouthEast
-                if y.checked_sub(1).is_some_and(|ym| ny == ym) =>
-            {
-                Some((nx, ny))
-            }
-            _ => None,
-        })
-        .collect::<Vec<_>>();
-
-    debug_assert!(start_pipes.len() == 2);
-
-    (start_pipes[0], start_pipes[1])
-}
+        .find(|&d| tile_at(map, start.step(d)).is_some_and(|t| get_exit_direction(d, t).is_some()))
+        .ok_or_else(|| anyhow!("start tile doesn't connect to any pipe"))?;
 
-// 1. Go both ways and count steps
-// 2. When you end up on same tile, return count of steps
+    let mut pos = start;
+    let mut path = vec![start];
 
-#[must_use]
-pub fn solve_part_1(p: &Problem) -> usize {
-    let Problem { map, start_pos } = p;
+    loop {
+        pos = pos.step(heading);
+
+        if pos == start {
+            break;
+        }
 
-    let (mut first_pipe_pos, mut second_pipe_pos) = get_start_pipes(map, *start_pos);
-    let mut first_pipe_entry = *start_pos;
-    let mut second_pipe_entry = *start_pos;
-
-    let mut result = 1;
-    while first_pipe_pos != second_pipe_pos {
-        let temp1 = first_pipe_pos;
-        first_pipe_pos = get_exit_pos(
-            first_pipe_entry,
-            first_pipe_pos,
-            &map[first_pipe_pos.0][first_pipe_pos.1],
-        );
-        first_pipe_entry = temp1;
-
-        let temp2 = second_pipe_pos;
-        second_pipe_pos = get_exit_pos(
-            second_pipe_entry,
-            second_pipe_pos,
-            &map[second_pipe_pos.0][second_pipe_pos.1],
-        );
-        second_pipe_entry = temp2;
-
-        result += 1;
+        path.push(pos);
+        heading = tile_at(map, pos)
+            .and_then(|t| get_exit_direction(heading, t))
+            .ok_or_else(|| anyhow!("pipe loop isn't closed at {:?}", pos))?;
     }
 
-    result
+    Ok(path)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// The farthest tile is halfway around the loop.
+///
+/// # Errors
+///
+/// Returns error when the start tile isn't part of a closed pipe loop.
+pub fn solve_part_1(p: &Problem) -> Result<usize, anyhow::Error> {
+    let Problem { map, start_pos } = p;
 
-    const TEST_INPUT: &str = "\
+    Ok(find_loop(map, *start_pos)?.len() / 2)
+}
+
+/// The loop is a polygon whose vertices are the centers of its tiles. Tiles enclosed by it are
+/// exactly the lattice points strictly inside, which Pick's theorem counts from the area and the
+/// number of boundary points, i.e. the loop length.
+///
+/// # Errors
+///
+/// Returns error when the start tile isn't part of a closed pipe loop.
+pub fn solve_part_2(p: &Problem) -> Result<u64, anyhow::Error> {
+    let Problem { map, start_pos } = p;
+
+    Ok(interior_points(&find_loop(map, *start_pos)?))
+}
+
+const TEST_INPUT: &str = "\
 -L|F7
 7S-7|
 L|7||
 -L-J|
 L|-JF";
 
+const TEST_INPUT_3: &str = "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = 10;
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT_3];
+    const LAYOUT: Layout = Layout::Grid;
+
+    fn part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_1(p)?.into())
+    }
+
+    fn part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_2(p)?.into())
+    }
+
+    fn reference_part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_1(p)?.into())
+    }
+
+    fn reference_part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_2(p)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     const TEST_INPUT_2: &str = "\
 7-F7-
 .FJ|7
@@ -261,21 +200,74 @@ SJLL7
 |F--J
 LJ.LJ";
 
+    const TEST_INPUT_4: &str = "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+
+    const TEST_INPUT_5: &str = "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
     #[test]
     fn test_input_parsing() {
         let p: Problem = TEST_INPUT.parse().unwrap();
         assert_eq!(p.start_pos, (1, 1));
-        assert_eq!(p.map.len(), 5);
-        assert_eq!(p.map[0].len(), 5);
-        assert_eq!(p.map[0][3], Tile::SouthEast);
-        assert_eq!(p.map[3][1], Tile::NorthEast);
+        assert_eq!(p.map.rows(), 5);
+        assert_eq!(p.map.cols(), 5);
+        assert_eq!(p.map[(0, 3)], Tile::SouthEast);
+        assert_eq!(p.map[(3, 1)], Tile::NorthEast);
     }
 
     #[test]
     fn test_solve_part_1() {
         let p1: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve_part_1(&p1), 4);
+        assert_eq!(solve_part_1(&p1).unwrap(), 4);
         let p2: Problem = TEST_INPUT_2.parse().unwrap();
-        assert_eq!(solve_part_1(&p2), 8);
+        assert_eq!(solve_part_1(&p2).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_broken_loop() {
+        let p: Problem = "S-7\n|.|\nL-J".parse().unwrap();
+        assert_eq!(solve_part_1(&p).unwrap(), 4);
+        let p: Problem = "S-7\n|.|\nL-.".parse().unwrap();
+        assert!(solve_part_1(&p).is_err());
+        let p: Problem = ".S.".parse().unwrap();
+        assert!(solve_part_2(&p).is_err());
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p3: Problem = TEST_INPUT_3.parse().unwrap();
+        assert_eq!(solve_part_2(&p3).unwrap(), 4);
+        let p4: Problem = TEST_INPUT_4.parse().unwrap();
+        assert_eq!(solve_part_2(&p4).unwrap(), 8);
+        let p5: Problem = TEST_INPUT_5.parse().unwrap();
+        assert_eq!(solve_part_2(&p5).unwrap(), 10);
+    }
+
+    #[test]
+    fn test_input_with_bom() {
+        // the mark would make the first row a tile longer than the others
+        let input = format!("\u{feff}{}\r\n", TEST_INPUT.replace('\n', "\r\n"));
+        let p: Problem = input.parse().unwrap();
+        assert_eq!(p.start_pos, (1, 1));
+        assert_eq!(solve_part_1(&p).unwrap(), 4);
     }
 }
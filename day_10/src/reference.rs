@@ -0,0 +1,119 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
+
+use std::collections::VecDeque;
+
+use anyhow::bail;
+use aoc_geometry::{Direction, Point};
+use aoc_grid::Grid;
+
+use crate::{tile_at, Problem};
+
+/// Directions the tile at `pos` leads to, telling the shape of the start tile by its neighbours
+/// leading back to it.
+fn connections(p: &Problem, pos: Point) -> Vec<Direction> {
+    if pos == Point::from_pos(p.start_pos) {
+        Direction::ALL
+            .into_iter()
+            .filter(|&d| {
+                tile_at(&p.map, pos.step(d))
+                    .and_then(|t| t.connections())
+                    .is_some_and(|c| c.contains(&d.opposite()))
+            })
+            .collect()
+    } else {
+        tile_at(&p.map, pos)
+            .and_then(|t| t.connections())
+            .map_or(vec![], Vec::from)
+    }
+}
+
+/// Distances along the loop from the start tile to every tile of it, found with breadth-first
+/// search.
+fn distances(p: &Problem) -> Result<Grid<Option<usize>>, anyhow::Error> {
+    let start = Point::from_pos(p.start_pos);
+
+    if connections(p, start).len() != 2 {
+        bail!("start tile doesn't connect to exactly two pipes");
+    }
+
+    let mut distances = p.map.map(|_| None);
+    let mut queue = VecDeque::from([(start, 0)]);
+    distances[p.start_pos] = Some(0);
+
+    while let Some((pos, distance)) = queue.pop_front() {
+        for d in connections(p, pos) {
+            let next = pos.step(d);
+
+            if !connections(p, next).contains(&d.opposite()) {
+                bail!("pipe loop isn't closed at {:?}", pos);
+            }
+
+            // the pipe leads back, so `next` lies within the map
+            if let Some(next_pos) = next.to_pos() {
+                if distances[next_pos].is_none() {
+                    distances[next_pos] = Some(distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+    }
+
+    Ok(distances)
+}
+
+/// # Errors
+///
+/// Returns error when the start tile isn't part of a closed pipe loop.
+pub fn solve_part_1(p: &Problem) -> Result<usize, anyhow::Error> {
+    Ok(distances(p)?
+        .iter()
+        .filter_map(|(_, &d)| d)
+        .max()
+        .unwrap_or(0))
+}
+
+/// Walks every row from the left, flipping between outside and inside each time the loop is
+/// crossed, i.e. on every tile of it leading north.
+///
+/// # Errors
+///
+/// Returns error when the start tile isn't part of a closed pipe loop.
+pub fn solve_part_2(p: &Problem) -> Result<u64, anyhow::Error> {
+    let on_loop = distances(p)?;
+    let mut enclosed = 0;
+
+    for i in 0..p.map.rows() {
+        let mut inside = false;
+
+        for j in 0..p.map.cols() {
+            if on_loop[(i, j)].is_none() {
+                enclosed += u64::from(inside);
+            } else if connections(p, Point::from_pos((i, j))).contains(&Direction::North) {
+                inside = !inside;
+            }
+        }
+    }
+
+    Ok(enclosed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TEST_INPUT, TEST_INPUT_3};
+
+    #[test]
+    fn test_solve_part_1() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_1(&p).unwrap(), 4);
+
+        let p: Problem = "S-7\n|.|\nL--".parse().unwrap();
+        assert!(solve_part_1(&p).is_err());
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p: Problem = TEST_INPUT_3.parse().unwrap();
+        assert_eq!(solve_part_2(&p).unwrap(), 4);
+    }
+}
@@ -1,8 +1,33 @@
 use std::str::FromStr;
 
-#[derive(Debug)]
+use anyhow::bail;
+use aoc_core::{Answer, Layout, Solution};
+use aoc_geometry::Point;
+use aoc_grid::Grid;
+
+pub mod reference;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Space {
+    Empty,
+    Galaxy,
+}
+
+impl TryFrom<char> for Space {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(Self::Empty),
+            '#' => Ok(Self::Galaxy),
+            c => bail!("couldn't parse Space from {:?}", c),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
 pub struct Problem {
-    map: Vec<Vec<bool>>,
+    map: Grid<Space>,
     galaxies: Vec<(usize, usize)>,
 }
 
@@ -10,19 +35,13 @@ impl FromStr for Problem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let map: Vec<Vec<_>> = s
-            .lines()
-            .map(|l| l.chars().map(|c| c == '#').collect())
-            .collect();
+        let s = &aoc_parse::normalize(s);
+
+        let map: Grid<Space> = s.parse()?;
 
         let galaxies = map
             .iter()
-            .enumerate()
-            .flat_map(|(i, v)| {
-                v.iter()
-                    .enumerate()
-                    .filter_map(move |(j, g)| g.then_some((i, j)))
-            })
+            .filter_map(|(pos, s)| (*s == Space::Galaxy).then_some(pos))
             .collect();
 
         Ok(Self { map, galaxies })
@@ -33,8 +52,8 @@ fn expand_space(p: &Problem, expansion_coeff: usize) -> Vec<(usize, usize)> {
     let Problem { map, galaxies } = p;
     let mut galaxies = galaxies.clone();
 
-    for i in (0..map.len()).rev() {
-        if map[i].iter().all(|g| !g) {
+    for i in (0..map.rows()).rev() {
+        if map.row(i).iter().all(|s| *s == Space::Empty) {
             for (x, _) in &mut galaxies {
                 if *x > i {
                     *x += expansion_coeff;
@@ -43,8 +62,8 @@ fn expand_space(p: &Problem, expansion_coeff: usize) -> Vec<(usize, usize)> {
         }
     }
 
-    for j in (0..map[0].len()).rev() {
-        if (0..map.len()).map(|i| map[i][j]).all(|g| !g) {
+    for j in (0..map.cols()).rev() {
+        if map.column(j).all(|s| *s == Space::Empty) {
             for (_, y) in &mut galaxies {
                 if *y > j {
                     *y += expansion_coeff;
@@ -56,16 +75,16 @@ fn expand_space(p: &Problem, expansion_coeff: usize) -> Vec<(usize, usize)> {
     galaxies
 }
 
-fn dist((a_x, a_y): &(usize, usize), (b_x, b_y): &(usize, usize)) -> usize {
-    a_x.abs_diff(*b_x) + a_y.abs_diff(*b_y)
-}
-
-fn pair_distance_sum(galaxies: &[(usize, usize)]) -> usize {
+fn pair_distance_sum(galaxies: &[(usize, usize)]) -> u64 {
+    let galaxies = galaxies
+        .iter()
+        .map(|&pos| Point::from_pos(pos))
+        .collect::<Vec<_>>();
     let mut result = 0;
 
     for (i, a) in galaxies.iter().enumerate() {
         for b in &galaxies[(i + 1)..] {
-            result += dist(a, b);
+            result += a.manhattan(*b);
         }
     }
 
@@ -73,24 +92,20 @@ fn pair_distance_sum(galaxies: &[(usize, usize)]) -> usize {
 }
 
 #[must_use]
-pub fn solve_part_1(p: &Problem) -> usize {
+pub fn solve_part_1(p: &Problem) -> u64 {
     let expanded_galaxies = expand_space(p, 1);
 
     pair_distance_sum(&expanded_galaxies)
 }
 
 #[must_use]
-pub fn solve_part_2(p: &Problem) -> usize {
+pub fn solve_part_2(p: &Problem) -> u64 {
     let expanded_galaxies = expand_space(p, 1_000_000 - 1);
 
     pair_distance_sum(&expanded_galaxies)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "\
+const TEST_INPUT: &str = "\
 ...#......
 .......#..
 #.........
@@ -102,14 +117,44 @@ mod tests {
 .......#..
 #...#.....";
 
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = 11;
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT];
+    const LAYOUT: Layout = Layout::Grid;
+
+    fn part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_1(p).into())
+    }
+
+    fn part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_2(p).into())
+    }
+
+    fn reference_part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_1(p).into())
+    }
+
+    fn reference_part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_2(p).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_input_parsing() {
         let p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(p.map.len(), 10);
-        assert_eq!(p.map[0].len(), 10);
+        assert_eq!(p.map.rows(), 10);
+        assert_eq!(p.map.cols(), 10);
         assert_eq!(p.galaxies.len(), 9);
-        assert!(!p.map[0][0]);
-        assert!(p.map[0][3]);
+        assert_eq!(p.map[(0, 0)], Space::Empty);
+        assert_eq!(p.map[(0, 3)], Space::Galaxy);
         assert_eq!(p.galaxies[0], (0, 3));
         assert_eq!(p.galaxies[8], (9, 4));
     }
@@ -138,10 +183,13 @@ mod tests {
     fn test_dist() {
         let p: Problem = TEST_INPUT.parse().unwrap();
         let expanded_galaxies = expand_space(&p, 1);
-        assert_eq!(dist(&expanded_galaxies[4], &expanded_galaxies[8]), 9);
-        assert_eq!(dist(&expanded_galaxies[0], &expanded_galaxies[6]), 15);
-        assert_eq!(dist(&expanded_galaxies[2], &expanded_galaxies[5]), 17);
-        assert_eq!(dist(&expanded_galaxies[7], &expanded_galaxies[8]), 5);
+        let dist = |i: usize, j: usize| {
+            Point::from_pos(expanded_galaxies[i]).manhattan(Point::from_pos(expanded_galaxies[j]))
+        };
+        assert_eq!(dist(4, 8), 9);
+        assert_eq!(dist(0, 6), 15);
+        assert_eq!(dist(2, 5), 17);
+        assert_eq!(dist(7, 8), 5);
     }
 
     #[test]
@@ -163,4 +211,12 @@ mod tests {
         let galaxies = expand_space(&p, 100 - 1);
         assert_eq!(pair_distance_sum(&galaxies), 8410);
     }
+
+    #[test]
+    fn test_input_with_bom() {
+        let input = format!("\u{feff}{}\r\n\r\n", TEST_INPUT.replace('\n', "\r\n"));
+        let p: Problem = input.parse().unwrap();
+        assert_eq!((p.map.rows(), p.map.cols()), (10, 10));
+        assert_eq!(solve_part_1(&p), 374);
+    }
 }
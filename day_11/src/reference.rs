@@ -0,0 +1,59 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
+
+use crate::{Problem, Space};
+
+/// Sum of distances between all pairs of galaxies, walking from one to the other a row and a
+/// column at a time, where crossing an empty one takes `factor` steps.
+fn distance_sum(p: &Problem, factor: u64) -> u64 {
+    let empty_rows = (0..p.map.rows())
+        .map(|i| p.map.row(i).iter().all(|s| *s == Space::Empty))
+        .collect::<Vec<_>>();
+    let empty_columns = (0..p.map.cols())
+        .map(|j| p.map.column(j).all(|s| *s == Space::Empty))
+        .collect::<Vec<_>>();
+
+    let steps = |empty: &[bool], from: usize, to: usize| -> u64 {
+        (from.min(to)..from.max(to))
+            .map(|k| if empty[k] { factor } else { 1 })
+            .sum()
+    };
+
+    let mut sum = 0;
+
+    for (i, &(a_row, a_col)) in p.galaxies.iter().enumerate() {
+        for &(b_row, b_col) in &p.galaxies[(i + 1)..] {
+            sum += steps(&empty_rows, a_row, b_row) + steps(&empty_columns, a_col, b_col);
+        }
+    }
+
+    sum
+}
+
+#[must_use]
+pub fn solve_part_1(p: &Problem) -> u64 {
+    distance_sum(p, 2)
+}
+
+#[must_use]
+pub fn solve_part_2(p: &Problem) -> u64 {
+    distance_sum(p, 1_000_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_INPUT;
+
+    #[test]
+    fn test_distance_sum() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(distance_sum(&p, 10), 1030);
+        assert_eq!(distance_sum(&p, 100), 8410);
+    }
+
+    #[test]
+    fn test_solve_part_1() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_1(&p), 374);
+    }
+}
@@ -1,7 +1,10 @@
 use std::str::FromStr;
 
-use anyhow::bail;
-use itertools::Itertools;
+use anyhow::{anyhow, bail};
+use aoc_core::{Answer, Solution};
+use aoc_parse::{FromSpan, ParseError, Span};
+
+pub mod reference;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum SpringState {
@@ -29,29 +32,30 @@ struct ConditionRecord {
     criteria: Vec<usize>,
 }
 
-impl FromStr for ConditionRecord {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let Some((record, criteria)) = s.split_once(' ') else {
-            bail!("couldn't split at ' '");
-        };
+impl FromSpan for ConditionRecord {
+    fn from_span(s: Span<'_>) -> Result<Self, ParseError> {
+        let (record, criteria) = s.split_once(" ")?;
 
-        let record = record
-            .chars()
-            .map(std::convert::TryInto::try_into)
-            .collect::<Result<_, _>>()?;
+        let record = record.parse_chars()?;
 
         let criteria = criteria
-            .split(',')
-            .map(str::parse)
+            .split(",")
+            .map(|c| c.parse())
             .collect::<Result<_, _>>()?;
 
         Ok(Self { record, criteria })
     }
 }
 
-#[derive(Debug)]
+impl FromStr for ConditionRecord {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        aoc_parse::parse(s)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
 pub struct Problem {
     records: Vec<ConditionRecord>,
 }
@@ -60,98 +64,32 @@ impl FromStr for Problem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = &aoc_parse::normalize(s);
+
         Ok(Self {
-            records: s.lines().map(str::parse).collect::<Result<_, _>>()?,
+            records: Span::new(s)
+                .lines()
+                .map(ConditionRecord::from_span)
+                .collect::<Result<_, _>>()?,
         })
     }
 }
 
-fn is_valid_arrangement(record: &[SpringState], criteria: &[usize]) -> bool {
-    let mut record = record.iter().peekable();
-
-    let mut criteria_ptr = 0;
-    let mut expected_damaged_springs: isize = (*criteria.get(criteria_ptr).unwrap_or(&0))
-        .try_into()
-        .unwrap();
-
-    loop {
-        while record.next_if_eq(&&SpringState::Operational).is_some() {}
-
-        if record.peek().is_none() {
-            break;
-        }
-
-        while record.next_if_eq(&&SpringState::Damaged).is_some() {
-            expected_damaged_springs -= 1;
-        }
-
-        if expected_damaged_springs == 0 {
-            criteria_ptr += 1;
-            expected_damaged_springs = (*criteria.get(criteria_ptr).unwrap_or(&0))
-                .try_into()
-                .unwrap();
-            continue;
-        }
-
-        return false;
-    }
-
-    criteria_ptr == criteria.len()
-}
-
-fn solve(record: &[SpringState], criteria: &[usize]) -> usize {
-    let mut result = 0;
-    let found_damaged = record
-        .iter()
-        .filter(|&s| *s == SpringState::Damaged)
-        .count();
-    let expected_damaged = criteria.iter().sum::<usize>();
-
-    let missing_damaged = expected_damaged - found_damaged;
-    let unknown_indices = record
-        .iter()
-        .enumerate()
-        .filter_map(|(i, s)| (*s == SpringState::Unknown).then_some(i));
-
-    for indices_to_damage in unknown_indices.combinations(missing_damaged) {
-        let mut new_record: Vec<_> = record
-            .iter()
-            .map(|s| {
-                if *s == SpringState::Unknown {
-                    SpringState::Operational
-                } else {
-                    *s
-                }
-            })
-            .collect();
-
-        for i in indices_to_damage {
-            new_record[i] = SpringState::Damaged;
-        }
-
-        if is_valid_arrangement(&new_record, criteria) {
-            result += 1;
-        }
-    }
-
-    result
-}
-
-// Part 1: brute force
-// Determine number of missing damaged springs, then change this many `?` to `#` and rest to `.`
-// and check if record is valid.
-
-#[must_use]
-pub fn solve_part_1(p: &Problem) -> usize {
+/// # Errors
+///
+/// Returns error when the number of arrangements doesn't fit in `usize`.
+pub fn solve_part_1(p: &Problem) -> Result<usize, anyhow::Error> {
     let Problem { records } = p;
 
-    let mut result = 0;
+    let mut result = 0usize;
 
     for ConditionRecord { record, criteria } in records {
-        result += solve(record, criteria);
+        result = count_arrangements(record, criteria)
+            .and_then(|n| result.checked_add(n))
+            .ok_or_else(|| anyhow!("number of arrangements overflows usize"))?;
     }
 
-    result
+    Ok(result)
 }
 
 fn unfold_record(r: ConditionRecord) -> ConditionRecord {
@@ -172,26 +110,63 @@ fn unfold_record(r: ConditionRecord) -> ConditionRecord {
     ConditionRecord { record, criteria }
 }
 
-#[must_use]
-pub fn solve_part_2(p: &Problem) -> usize {
+// Count arrangements with dynamic programming, brute force doesn't stand a chance against
+// unfolded records. `ways[i][j]` is the number of arrangements of `record[i..]`
+// satisfying `criteria[j..]`; a group of damaged springs starting at `i` must be followed by an
+// operational spring (or the end of the record), hence the extra row at `n + 1`. Returns `None`
+// when the count overflows.
+fn count_arrangements(record: &[SpringState], criteria: &[usize]) -> Option<usize> {
+    let n = record.len();
+    let m = criteria.len();
+
+    let mut ways = vec![vec![0usize; m + 1]; n + 2];
+    ways[n][m] = 1;
+    ways[n + 1][m] = 1;
+
+    for i in (0..n).rev() {
+        for j in 0..=m {
+            if record[i] != SpringState::Damaged {
+                ways[i][j] = ways[i][j].checked_add(ways[i + 1][j])?;
+            }
+
+            if record[i] != SpringState::Operational && j < m {
+                let end = i.saturating_add(criteria[j]);
+
+                if end <= n
+                    && record[i..end]
+                        .iter()
+                        .all(|&s| s != SpringState::Operational)
+                    && record.get(end) != Some(&SpringState::Damaged)
+                {
+                    ways[i][j] = ways[i][j].checked_add(ways[end + 1][j + 1])?;
+                }
+            }
+        }
+    }
+
+    Some(ways[0][0])
+}
+
+/// # Errors
+///
+/// Returns error when the number of arrangements doesn't fit in `usize`.
+pub fn solve_part_2(p: &Problem) -> Result<usize, anyhow::Error> {
     let Problem { records } = p;
 
-    let mut result = 0;
+    let mut result = 0usize;
 
     for record in records {
         let ConditionRecord { record, criteria } = unfold_record(record.clone());
 
-        result += solve(&record, &criteria);
+        result = count_arrangements(&record, &criteria)
+            .and_then(|n| result.checked_add(n))
+            .ok_or_else(|| anyhow!("number of arrangements overflows usize"))?;
     }
 
-    result
+    Ok(result)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "\
+const TEST_INPUT: &str = "\
 ???.### 1,1,3
 .??..??...?##. 1,1,3
 ?#?#?#?#?#?#?#? 1,3,1,6
@@ -199,6 +174,35 @@ mod tests {
 ????.######..#####. 1,6,5
 ?###???????? 3,2,1";
 
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = 12;
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT];
+
+    fn part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_1(p)?.into())
+    }
+
+    fn part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_2(p)?.into())
+    }
+
+    fn reference_part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_1(p).into())
+    }
+
+    fn reference_part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_2(p)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_input_parsing() {
         let p: Problem = TEST_INPUT.parse().unwrap();
@@ -218,34 +222,77 @@ mod tests {
                 criteria: vec![1, 1, 3]
             }
         );
+
+        let e = "???.### 1,1,3\n.??.?x 1,1".parse::<Problem>().unwrap_err();
+        assert!(e
+            .to_string()
+            .starts_with("line 2, column 6: couldn't parse SpringState from 'x'"));
+        assert!("???.### 1,,3".parse::<ConditionRecord>().is_err());
     }
 
     #[test]
-    fn test_is_valid_arrangement() {
-        // valid
-        let ConditionRecord { criteria, record } = ".###.##.#... 3,2,1".parse().unwrap();
-        assert!(is_valid_arrangement(&record, &criteria));
-        let ConditionRecord { criteria, record } = ".###..##...# 3,2,1".parse().unwrap();
-        assert!(is_valid_arrangement(&record, &criteria));
-
-        // invalid
-        let ConditionRecord { criteria, record } = ".#####...# 3,2,1".parse().unwrap();
-        assert!(!is_valid_arrangement(&record, &criteria));
-        let ConditionRecord { criteria, record } = ".###...## 3,2,1".parse().unwrap();
-        assert!(!is_valid_arrangement(&record, &criteria));
-        let ConditionRecord { criteria, record } = ".######. 3,2,1".parse().unwrap();
-        assert!(!is_valid_arrangement(&record, &criteria));
+    fn test_solve_part_1() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_1(&p).unwrap(), 21);
     }
 
     #[test]
-    fn test_solve_part_1() {
+    fn test_count_arrangements() {
         let p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve_part_1(&p), 21);
+        for ConditionRecord { record, criteria } in &p.records {
+            assert_eq!(
+                count_arrangements(record, criteria),
+                Some(reference::solve(record, criteria))
+            );
+        }
+
+        // more damaged springs than the record has room for
+        let ConditionRecord { criteria, record } = "??#?# 1,4294967295".parse().unwrap();
+        assert_eq!(reference::solve(&record, &criteria), 0);
+        assert_eq!(count_arrangements(&record, &criteria), Some(0));
+        let ConditionRecord { criteria, record } = "###.# 1,1".parse().unwrap();
+        assert_eq!(reference::solve(&record, &criteria), 0);
+
+        let p: Problem = format!("{} {}", "?".repeat(100), ["1"; 10].join(","))
+            .parse()
+            .unwrap();
+        assert!(solve_part_2(&p).is_err());
+    }
+
+    #[test]
+    fn test_count_arrangements_unfolded() {
+        // examples small enough to brute force even unfolded, and every short record
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        let mut records = [0, 3, 4].map(|i| p.records[i].clone()).to_vec();
+
+        for record in [
+            "?", ".", "#", "??", "?.", "?#", ".?", "#?", "#.", ".#", "##",
+        ] {
+            for criteria in ["1", "2", "1,1"] {
+                records.push(format!("{record} {criteria}").parse().unwrap());
+            }
+        }
+
+        for record in records {
+            let ConditionRecord { record, criteria } = unfold_record(record);
+            assert_eq!(
+                count_arrangements(&record, &criteria),
+                Some(reference::solve(&record, &criteria)),
+                "{record:?} {criteria:?}"
+            );
+        }
     }
 
     #[test]
     fn test_solve_part_2() {
         let p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve_part_2(&p), 525_152);
+        assert_eq!(solve_part_2(&p).unwrap(), 525_152);
+    }
+
+    #[test]
+    fn test_trailing_whitespace() {
+        let p: Problem = TEST_INPUT.replace('\n', " \r\n").parse().unwrap();
+        assert_eq!(p.records[0].criteria, vec![1, 1, 3]);
+        assert_eq!(solve_part_1(&p).unwrap(), 21);
     }
 }
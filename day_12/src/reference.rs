@@ -0,0 +1,207 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
+
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use itertools::Itertools;
+
+use crate::{unfold_record, ConditionRecord, Problem, SpringState};
+
+fn is_valid_arrangement(record: &[SpringState], criteria: &[usize]) -> bool {
+    let mut record = record.iter().peekable();
+
+    let mut criteria_ptr = 0;
+    let mut expected_damaged_springs: isize = (*criteria.get(criteria_ptr).unwrap_or(&0))
+        .try_into()
+        .unwrap();
+
+    loop {
+        while record.next_if_eq(&&SpringState::Operational).is_some() {}
+
+        if record.peek().is_none() {
+            break;
+        }
+
+        while record.next_if_eq(&&SpringState::Damaged).is_some() {
+            expected_damaged_springs -= 1;
+        }
+
+        if expected_damaged_springs == 0 {
+            criteria_ptr += 1;
+            expected_damaged_springs = (*criteria.get(criteria_ptr).unwrap_or(&0))
+                .try_into()
+                .unwrap();
+            continue;
+        }
+
+        return false;
+    }
+
+    criteria_ptr == criteria.len()
+}
+
+pub(crate) fn solve(record: &[SpringState], criteria: &[usize]) -> usize {
+    let mut result = 0;
+    let found_damaged = record
+        .iter()
+        .filter(|&s| *s == SpringState::Damaged)
+        .count();
+    let unknown_indices = record
+        .iter()
+        .enumerate()
+        .filter_map(|(i, s)| (*s == SpringState::Unknown).then_some(i));
+
+    // criteria asking for more damaged springs than the record can hold can't be met
+    let Some(missing_damaged) = criteria
+        .iter()
+        .try_fold(0usize, |acc, &c| acc.checked_add(c))
+        .and_then(|expected_damaged| expected_damaged.checked_sub(found_damaged))
+        .filter(|&missing| missing <= unknown_indices.clone().count())
+    else {
+        return 0;
+    };
+
+    for indices_to_damage in unknown_indices.combinations(missing_damaged) {
+        let mut new_record: Vec<_> = record
+            .iter()
+            .map(|s| {
+                if *s == SpringState::Unknown {
+                    SpringState::Operational
+                } else {
+                    *s
+                }
+            })
+            .collect();
+
+        for i in indices_to_damage {
+            new_record[i] = SpringState::Damaged;
+        }
+
+        if is_valid_arrangement(&new_record, criteria) {
+            result += 1;
+        }
+    }
+
+    result
+}
+
+// Part 1: brute force
+// Determine number of missing damaged springs, then change this many `?` to `#` and rest to `.`
+// and check if record is valid.
+
+#[must_use]
+pub fn solve_part_1(p: &Problem) -> usize {
+    let Problem { records } = p;
+
+    let mut result = 0;
+
+    for ConditionRecord { record, criteria } in records {
+        result += solve(record, criteria);
+    }
+
+    result
+}
+
+// Part 2: brute force doesn't stand a chance against unfolded records, so memoize the plainest
+// recursion there is instead: the first spring is either operational, or starts the first group
+// of damaged ones. Records and criteria are only ever cut at the front, so their lengths make a
+// key. Returns `None` when the count overflows.
+fn count(
+    record: &[SpringState],
+    criteria: &[usize],
+    memo: &mut HashMap<(usize, usize), usize>,
+) -> Option<usize> {
+    let Some(&first) = record.first() else {
+        return Some(usize::from(criteria.is_empty()));
+    };
+
+    if let Some(&n) = memo.get(&(record.len(), criteria.len())) {
+        return Some(n);
+    }
+
+    let mut n = 0usize;
+
+    if first != SpringState::Damaged {
+        n = n.checked_add(count(&record[1..], criteria, memo)?)?;
+    }
+
+    if let Some(&group) = criteria.first() {
+        if first != SpringState::Operational
+            && group <= record.len()
+            && record[..group]
+                .iter()
+                .all(|&s| s != SpringState::Operational)
+            && record.get(group) != Some(&SpringState::Damaged)
+        {
+            let rest = record.get((group + 1)..).unwrap_or(&[]);
+            n = n.checked_add(count(rest, &criteria[1..], memo)?)?;
+        }
+    }
+
+    memo.insert((record.len(), criteria.len()), n);
+
+    Some(n)
+}
+
+/// # Errors
+///
+/// Returns error when the number of arrangements doesn't fit in `usize`.
+pub fn solve_part_2(p: &Problem) -> Result<usize, anyhow::Error> {
+    let mut result = 0usize;
+
+    for record in &p.records {
+        let ConditionRecord { record, criteria } = unfold_record(record.clone());
+
+        result = count(&record, &criteria, &mut HashMap::new())
+            .and_then(|n| result.checked_add(n))
+            .ok_or_else(|| anyhow!("number of arrangements overflows usize"))?;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_INPUT;
+
+    #[test]
+    fn test_is_valid_arrangement() {
+        // valid
+        let ConditionRecord { criteria, record } = ".###.##.#... 3,2,1".parse().unwrap();
+        assert!(is_valid_arrangement(&record, &criteria));
+        let ConditionRecord { criteria, record } = ".###..##...# 3,2,1".parse().unwrap();
+        assert!(is_valid_arrangement(&record, &criteria));
+
+        // invalid
+        let ConditionRecord { criteria, record } = ".#####...# 3,2,1".parse().unwrap();
+        assert!(!is_valid_arrangement(&record, &criteria));
+        let ConditionRecord { criteria, record } = ".###...## 3,2,1".parse().unwrap();
+        assert!(!is_valid_arrangement(&record, &criteria));
+        let ConditionRecord { criteria, record } = ".######. 3,2,1".parse().unwrap();
+        assert!(!is_valid_arrangement(&record, &criteria));
+    }
+
+    #[test]
+    fn test_solve_part_1() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_1(&p), 21);
+    }
+
+    #[test]
+    fn test_count() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        for ConditionRecord { record, criteria } in &p.records {
+            assert_eq!(
+                count(record, criteria, &mut HashMap::new()),
+                Some(solve(record, criteria))
+            );
+        }
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_2(&p).unwrap(), 525_152);
+    }
+}
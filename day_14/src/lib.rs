@@ -1,6 +1,11 @@
 use std::{cmp::Reverse, collections::BinaryHeap, str::FromStr};
 
 use anyhow::bail;
+use aoc_core::{Answer, Layout, Solution};
+use aoc_cycle::state_after;
+use aoc_grid::Grid;
+
+pub mod reference;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Cell {
@@ -22,63 +27,48 @@ impl TryFrom<char> for Cell {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Problem {
-    map: Vec<Vec<Cell>>,
+    map: Grid<Cell>,
 }
 
 impl FromStr for Problem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let map = s
-            .lines()
-            .map(|l| l.chars().map(std::convert::TryInto::try_into).collect())
-            .collect::<Result<_, _>>()?;
+        let s = &aoc_parse::normalize(s);
+
+        let map = s.parse()?;
 
         Ok(Self { map })
     }
 }
 
-fn slide_north(map: &[Vec<Cell>]) -> Vec<Vec<Cell>> {
-    let mut map = map.to_owned();
-    let rows = map.len();
+fn slide_north(map: &Grid<Cell>) -> Grid<Cell> {
+    let mut map = map.clone();
+    let rows = map.rows();
 
-    let cube_indices_in_row = (0..map.len())
-        .map(|i| {
-            map[i]
-                .iter()
-                .filter_map(move |c| (*c == Cell::CubeRock).then_some(i))
+    let cube_indices_in_col = map
+        .iter_columns()
+        .map(|column| {
+            column
+                .enumerate()
+                .filter_map(|(i, c)| (*c == Cell::CubeRock).then_some(i))
                 .collect()
         })
         .collect::<Vec<Vec<_>>>();
 
-    let cube_indices_in_col = (0..map[0].len())
-        .map(|j| {
-            (0..map.len())
-                .filter(|&i| map[i][j] == Cell::CubeRock)
-                .collect()
-        })
-        .collect::<Vec<Vec<_>>>();
-
-    let mut round_indices_in_row = (0..map.len())
-        .map(|i| {
-            map[i]
-                .iter()
-                .filter_map(move |c| (*c == Cell::RoundRock).then_some(Reverse(i)))
+    let round_indices_in_col = map
+        .iter_columns()
+        .map(|column| {
+            column
+                .enumerate()
+                .filter_map(|(i, c)| (*c == Cell::RoundRock).then_some(Reverse(i)))
                 .collect::<BinaryHeap<_>>()
         })
         .collect::<Vec<_>>();
 
-    let mut round_indices_in_col = (0..map[0].len())
-        .map(|j| {
-            (0..map.len())
-                .filter_map(|i| (map[i][j] == Cell::RoundRock).then_some(Reverse(i)))
-                .collect::<BinaryHeap<_>>()
-        })
-        .collect::<Vec<_>>();
-
-    for col in 0..map[0].len() {
+    for col in 0..map.cols() {
         let cube_indices = &cube_indices_in_col[col];
         let round_indices = &round_indices_in_col[col];
 
@@ -93,8 +83,8 @@ fn slide_north(map: &[Vec<Cell>]) -> Vec<Vec<Cell>> {
                 .is_some_and(|&Reverse(i)| i < cube_indices.peek().unwrap_or(&&usize::MAX))
             {
                 let round_idx = round_indices.next().unwrap().0;
-                map[round_idx][col] = Cell::Empty;
-                map[fall_to_idx][col] = Cell::RoundRock;
+                map[(round_idx, col)] = Cell::Empty;
+                map[(fall_to_idx, col)] = Cell::RoundRock;
                 fall_to_idx += 1;
             }
 
@@ -111,10 +101,15 @@ fn slide_north(map: &[Vec<Cell>]) -> Vec<Vec<Cell>> {
     map
 }
 
-fn calculate_load(map: &[Vec<Cell>]) -> usize {
-    let rows = map.len();
+fn spin_cycle(map: &Grid<Cell>) -> Grid<Cell> {
+    // tilting north after each clockwise turn tilts west, south and east in turn
+    (0..4).fold(map.clone(), |map, _| slide_north(&map).rotate_clockwise())
+}
+
+fn calculate_load(map: &Grid<Cell>) -> usize {
+    let rows = map.rows();
 
-    map.iter()
+    map.iter_rows()
         .enumerate()
         .map(|(i, r)| r.iter().filter(|&c| *c == Cell::RoundRock).count() * (rows - i))
         .sum()
@@ -129,11 +124,16 @@ pub fn solve_part_1(p: &Problem) -> usize {
     calculate_load(&map)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[must_use]
+pub fn solve_part_2(p: &Problem) -> usize {
+    let Problem { map } = p;
+
+    let map = state_after(map.clone(), spin_cycle, 1_000_000_000);
+
+    calculate_load(&map)
+}
 
-    const TEST_INPUT: &str = "\
+const TEST_INPUT: &str = "\
 O....#....
 O.OO#....#
 .....##...
@@ -145,24 +145,54 @@ O.#..O.#.#
 #....###..
 #OO..#....";
 
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = 14;
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT];
+    const LAYOUT: Layout = Layout::Grid;
+
+    fn part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_1(p).into())
+    }
+
+    fn part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_2(p).into())
+    }
+
+    fn reference_part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_1(p).into())
+    }
+
+    fn reference_part_2(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_2(p).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_input_parsing() {
         let p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(p.map.len(), 10);
-        assert_eq!(p.map[0].len(), 10);
-        assert_eq!(p.map[0][5], Cell::CubeRock);
-        assert_eq!(p.map[3][0], Cell::RoundRock);
-        assert_eq!(p.map[0][1], Cell::Empty);
+        assert_eq!(p.map.rows(), 10);
+        assert_eq!(p.map.cols(), 10);
+        assert_eq!(p.map[(0, 5)], Cell::CubeRock);
+        assert_eq!(p.map[(3, 0)], Cell::RoundRock);
+        assert_eq!(p.map[(0, 1)], Cell::Empty);
     }
 
     #[test]
     fn test_slide_north() {
         let p: Problem = TEST_INPUT.parse().unwrap();
         let map = slide_north(&p.map);
-        assert_eq!(map[0][1], Cell::RoundRock);
-        assert_eq!(map[0][4], Cell::Empty);
-        assert_eq!(map[1][4], Cell::CubeRock);
-        assert_eq!(map[2][4], Cell::RoundRock);
+        assert_eq!(map[(0, 1)], Cell::RoundRock);
+        assert_eq!(map[(0, 4)], Cell::Empty);
+        assert_eq!(map[(1, 4)], Cell::CubeRock);
+        assert_eq!(map[(2, 4)], Cell::RoundRock);
     }
 
     #[test]
@@ -170,4 +200,37 @@ O.#..O.#.#
         let p: Problem = TEST_INPUT.parse().unwrap();
         assert_eq!(solve_part_1(&p), 136);
     }
+
+    #[test]
+    fn test_spin_cycle() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        let map = spin_cycle(&p.map);
+        let expected: Grid<Cell> = "\
+.....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#...."
+            .parse()
+            .unwrap();
+        assert_eq!(map, expected);
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_2(&p), 64);
+    }
+
+    #[test]
+    fn test_trailing_blank_lines() {
+        let p: Problem = format!("{TEST_INPUT}\r\n\r\n\n").parse().unwrap();
+        assert_eq!(p.map.rows(), 10);
+        assert_eq!(solve_part_1(&p), 136);
+    }
 }
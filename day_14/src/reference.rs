@@ -0,0 +1,93 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
+
+use aoc_geometry::{Direction, Point};
+use aoc_grid::Grid;
+
+use crate::{Cell, Problem};
+
+/// Moves round rocks a single cell at a time, for as long as any of them can move.
+fn tilt(map: &Grid<Cell>, direction: Direction) -> Grid<Cell> {
+    let mut map = map.clone();
+
+    loop {
+        let mut moved = false;
+
+        for i in 0..map.rows() {
+            for j in 0..map.cols() {
+                let Some(next) = Point::from_pos((i, j)).step(direction).to_pos() else {
+                    continue;
+                };
+
+                if map[(i, j)] == Cell::RoundRock && map.get(next) == Some(&Cell::Empty) {
+                    map.swap((i, j), next);
+                    moved = true;
+                }
+            }
+        }
+
+        if !moved {
+            return map;
+        }
+    }
+}
+
+fn spin_cycle(map: &Grid<Cell>) -> Grid<Cell> {
+    [
+        Direction::North,
+        Direction::West,
+        Direction::South,
+        Direction::East,
+    ]
+    .into_iter()
+    .fold(map.clone(), |map, direction| tilt(&map, direction))
+}
+
+fn load(map: &Grid<Cell>) -> usize {
+    map.iter()
+        .filter(|&(_, &c)| c == Cell::RoundRock)
+        .map(|((i, _), _)| map.rows() - i)
+        .sum()
+}
+
+#[must_use]
+pub fn solve_part_1(p: &Problem) -> usize {
+    load(&tilt(&p.map, Direction::North))
+}
+
+/// Spins until a state comes up again, which it has to, as there are only so many of them. From
+/// then on states repeat in a loop, which tells the one after all the spins.
+#[must_use]
+pub fn solve_part_2(p: &Problem) -> usize {
+    const SPINS: usize = 1_000_000_000;
+
+    let mut history = vec![p.map.clone()];
+
+    loop {
+        let next = spin_cycle(&history[history.len() - 1]);
+
+        if let Some(start) = history.iter().position(|m| *m == next) {
+            let period = history.len() - start;
+            return load(&history[start + (SPINS - start) % period]);
+        }
+
+        history.push(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_INPUT;
+
+    #[test]
+    fn test_solve_part_1() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_1(&p), 136);
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_2(&p), 64);
+    }
+}
@@ -1,6 +1,12 @@
 use std::{collections::HashMap, str::FromStr};
 
 use anyhow::{anyhow, bail};
+use aoc_core::{trace, Answer, Layout, Part, Solution};
+use aoc_graph::topological_sort;
+use aoc_math::checked_sum;
+use aoc_parse::{FromSpan, ParseError, Span};
+
+pub mod reference;
 
 #[derive(Debug, Hash, PartialEq, Eq)]
 enum Property {
@@ -35,17 +41,6 @@ impl From<&Property> for usize {
     }
 }
 
-impl FromStr for Property {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.chars()
-            .next()
-            .and_then(|c| c.try_into().ok())
-            .ok_or_else(|| anyhow!("couldn't parse property from {:?}", s))
-    }
-}
-
 #[derive(Debug, PartialEq, Eq)]
 enum Relation {
     GT,
@@ -72,44 +67,23 @@ struct Rule {
     target_workflow_name: String,
 }
 
-impl FromStr for Rule {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (check, target_workflow_name) = s
-            .split_once(':')
-            .ok_or_else(|| anyhow!("couldn't split rule at ':'"))?;
-
-        let target_workflow_name = target_workflow_name.to_string();
+impl FromSpan for Rule {
+    fn from_span(s: Span<'_>) -> Result<Self, ParseError> {
+        let (check, target_workflow_name) = s.split_once(":")?;
 
-        let contains_greater_than = check.contains(|c| c == '>');
+        let relation_idx = check
+            .as_str()
+            .find(['<', '>'])
+            .ok_or_else(|| check.error("expected '<' or '>'"))?;
 
-        let (property, value) = if contains_greater_than {
-            check.split_once('>')
-        } else {
-            check.split_once('<')
-        }
-        .ok_or_else(|| anyhow!("couldn't split at '>' or '<'"))?;
-
-        let property = property
-            .chars()
-            .nth(0)
-            .and_then(|c| c.try_into().ok())
-            .ok_or_else(|| anyhow!("couldn't parse Property"))?;
-
-        let relation = if contains_greater_than {
-            Relation::GT
-        } else {
-            Relation::LT
-        };
-
-        let value = value.parse()?;
+        let (property, rest) = check.split_at(relation_idx);
+        let (relation, value) = rest.split_at(1);
 
         Ok(Self {
-            property,
-            relation,
-            value,
-            target_workflow_name,
+            property: property.parse_char()?,
+            relation: relation.parse_char()?,
+            value: value.parse()?,
+            target_workflow_name: target_workflow_name.as_str().to_string(),
         })
     }
 }
@@ -121,67 +95,74 @@ struct Workflow {
     fallback_workflow_name: String,
 }
 
-impl FromStr for Workflow {
-    type Err = anyhow::Error;
+impl FromSpan for Workflow {
+    fn from_span(s: Span<'_>) -> Result<Self, ParseError> {
+        let (name, rules) = s.strip_suffix("}")?.split_once("{")?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split_terminator(&['{', '}'][..]);
-
-        let name = parts
-            .next()
-            .ok_or_else(|| anyhow!("missing name"))?
-            .to_string();
-
-        let mut rules = parts
-            .next()
-            .ok_or_else(|| anyhow!("missing rules"))?
-            .split(',')
-            .rev();
+        let mut rules = rules.split(",").collect::<Vec<_>>();
 
         let fallback_workflow_name = rules
-            .next()
-            .ok_or_else(|| anyhow!("missing fallback workflow"))?
-            .to_string();
+            .pop()
+            .filter(|f| !f.is_empty())
+            .ok_or_else(|| s.error_at(s.as_str().len() - 1, "missing fallback workflow"))?;
 
-        let rules = rules.rev().map(str::parse).collect::<Result<_, _>>()?;
+        let rules = rules
+            .into_iter()
+            .map(Rule::from_span)
+            .collect::<Result<_, _>>()?;
 
         Ok(Self {
-            name,
+            name: name.as_str().to_string(),
             rules,
-            fallback_workflow_name,
+            fallback_workflow_name: fallback_workflow_name.as_str().to_string(),
         })
     }
 }
 
-#[derive(Debug)]
-struct PartRating([u64; 4]);
-
-impl FromStr for PartRating {
-    type Err = anyhow::Error;
+impl FromStr for Workflow {
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let ratings = s
-            .split_terminator(&['{', '}', ','][..])
-            .filter(|p| !p.is_empty())
-            .map(|property_value_pair| -> Result<u64, anyhow::Error> {
-                let (_, value) = property_value_pair
-                    .split_once('=')
-                    .ok_or_else(|| anyhow!("couldn't split at '='"))?;
+        aoc_parse::parse(s)
+    }
+}
 
-                let value = value.parse::<u64>()?;
+impl Workflow {
+    fn targets(&self) -> impl Iterator<Item = &str> {
+        self.rules
+            .iter()
+            .map(|r| r.target_workflow_name.as_str())
+            .chain([self.fallback_workflow_name.as_str()])
+    }
+}
 
-                Ok(value)
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+#[derive(Debug, PartialEq, Eq)]
+struct PartRating([u64; 4]);
+
+impl FromSpan for PartRating {
+    fn from_span(s: Span<'_>) -> Result<Self, ParseError> {
+        let mut ratings = [None; 4];
+
+        for (property, value) in s
+            .strip_prefix("{")?
+            .strip_suffix("}")?
+            .key_values(",", "=")?
+        {
+            let property_idx = usize::from(&property.parse_char::<Property>()?);
 
-        let ratings: Result<[u64; 4], _> = ratings.try_into();
-        let ratings = ratings.unwrap();
+            if ratings[property_idx].replace(value.parse()?).is_some() {
+                return Err(property.error("duplicate rating"));
+            }
+        }
 
-        Ok(Self(ratings))
+        match ratings {
+            [Some(x), Some(m), Some(a), Some(s)] => Ok(Self([x, m, a, s])),
+            _ => Err(s.error("expected ratings of x, m, a and s")),
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Problem {
     workflows: HashMap<String, Workflow>,
     part_ratings: Vec<PartRating>,
@@ -191,18 +172,27 @@ impl FromStr for Problem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (workflows, part_ratings) = s
-            .split_once("\n\n")
-            .ok_or_else(|| anyhow!("couldn't split at double newline"))?;
+        let s = &aoc_parse::normalize(s);
+
+        let s = Span::new(s);
+        let mut sections = s.sections();
+
+        let (Some(workflows), Some(part_ratings), None) =
+            (sections.next(), sections.next(), sections.next())
+        else {
+            bail!(s.error("expected workflows and part ratings separated by a blank line"));
+        };
 
         let workflows = workflows
             .lines()
-            .map(|l| l.parse::<Workflow>().map(|w| (w.name.clone(), w)))
+            .map(|l| Workflow::from_span(l).map(|w| (w.name.clone(), w)))
             .collect::<Result<HashMap<String, Workflow>, _>>()?;
 
+        check_workflows(&workflows)?;
+
         let part_ratings = part_ratings
             .lines()
-            .map(str::parse)
+            .map(PartRating::from_span)
             .collect::<Result<Vec<PartRating>, _>>()?;
 
         Ok(Self {
@@ -212,6 +202,38 @@ impl FromStr for Problem {
     }
 }
 
+// Every part has to end up accepted or rejected, so evaluation mustn't get stuck on an unknown
+// workflow or go around in circles.
+fn check_workflows(workflows: &HashMap<String, Workflow>) -> Result<(), anyhow::Error> {
+    if !workflows.contains_key("in") {
+        bail!("missing workflow \"in\"");
+    }
+
+    for workflow in workflows.values() {
+        if let Some(target) = workflow
+            .targets()
+            .find(|&t| t != "A" && t != "R" && !workflows.contains_key(t))
+        {
+            bail!(
+                "workflow {:?} refers to unknown workflow {:?}",
+                workflow.name,
+                target
+            );
+        }
+    }
+
+    topological_sort(["in"], |&name| {
+        workflows
+            .get(name)
+            .into_iter()
+            .flat_map(Workflow::targets)
+            .collect::<Vec<_>>()
+    })
+    .map_err(|cycle| anyhow!("workflows can loop forever, {}", cycle))?;
+
+    Ok(())
+}
+
 fn next_workflow(part: &PartRating, workflow: &Workflow) -> String {
     let Workflow {
         rules,
@@ -254,9 +276,19 @@ enum EvaluationResult {
 
 fn evaluate_part(part: &PartRating, workflows: &HashMap<String, Workflow>) -> EvaluationResult {
     let mut current_workflow_name = "in".to_string();
+    let mut path = trace::enabled().then(|| vec![current_workflow_name.clone()]);
 
     while current_workflow_name != "A" && current_workflow_name != "R" {
         current_workflow_name = next_workflow(part, &workflows[&current_workflow_name]);
+
+        if let Some(path) = &mut path {
+            path.push(current_workflow_name.clone());
+        }
+    }
+
+    if let Some(path) = path {
+        let [x, m, a, s] = part.0;
+        trace!("{{x={x},m={m},a={a},s={s}}}: {}", path.join(" -> "));
     }
 
     if current_workflow_name == "A" {
@@ -266,8 +298,10 @@ fn evaluate_part(part: &PartRating, workflows: &HashMap<String, Workflow>) -> Ev
     }
 }
 
-#[must_use]
-pub fn solve_part_1(p: &Problem) -> u64 {
+/// # Errors
+///
+/// Returns error when the sum of ratings of accepted parts overflows.
+pub fn solve_part_1(p: &Problem) -> Result<u64, anyhow::Error> {
     let Problem {
         workflows,
         part_ratings,
@@ -276,15 +310,12 @@ pub fn solve_part_1(p: &Problem) -> u64 {
     part_ratings
         .iter()
         .filter(|&part| evaluate_part(part, workflows) == EvaluationResult::Accepted)
-        .map(|part| part.0[0] + part.0[1] + part.0[2] + part.0[3])
-        .sum()
+        .map(|part| checked_sum(part.0))
+        .try_fold(0u64, |acc, sum| acc.checked_add(sum?))
+        .ok_or_else(|| anyhow!("sum of ratings overflows u64"))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = "\
+const TEST_INPUT: &str = "\
 px{a<2006:qkq,m>2090:A,rfg}
 pv{a>1716:R,A}
 lnx{m>1548:A,A}
@@ -303,6 +334,29 @@ hdj{m>838:A,pv}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}";
 
+pub struct Day;
+
+impl Solution for Day {
+    type Problem = Problem;
+
+    const DAY: u8 = 19;
+    const IMPLEMENTED: &'static [Part] = &[Part::One];
+    const EXAMPLES: [&'static str; 2] = [TEST_INPUT, TEST_INPUT];
+    const LAYOUT: Layout = Layout::Sections { header: 0 };
+
+    fn part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(solve_part_1(p)?.into())
+    }
+
+    fn reference_part_1(p: &Problem) -> Result<Answer, anyhow::Error> {
+        Ok(reference::solve_part_1(p)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_workflow_parsing() {
         let w: Workflow = "px{a<2006:qkq,m>2090:A,rfg}".parse().unwrap();
@@ -327,11 +381,87 @@ hdj{m>838:A,pv}
                 fallback_workflow_name: "rfg".to_string(),
             }
         );
+
+        let e = "px{a<2006:qkq,m2090:A,rfg}"
+            .parse::<Workflow>()
+            .unwrap_err();
+        assert_eq!((e.line, e.column), (1, 15));
+        assert_eq!(e.message, "expected '<' or '>'");
+        assert!("px{a<2006:qkq,}".parse::<Workflow>().is_err());
+        assert!("px{a<20x6:qkq,A}".parse::<Workflow>().is_err());
+        assert!("px{y<2006:qkq,A}".parse::<Workflow>().is_err());
+    }
+
+    #[test]
+    fn test_part_rating_parsing() {
+        let workflows = "in{x>10:A,R}\n\n";
+
+        let p: Problem = format!("{workflows}{{s=1,a=2,m=3,x=4}}").parse().unwrap();
+        assert_eq!(p.part_ratings[0].0, [4, 3, 2, 1]);
+
+        let e = format!("{workflows}{{x=1,m=2,a=3,s=4}}\n{{x=1,m=2,a=3}}")
+            .parse::<Problem>()
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!((e.line, e.column), (4, 1));
+        assert_eq!(e.message, "expected ratings of x, m, a and s");
+
+        let e = format!("{workflows}{{x=1,m=2,x=3,s=4}}")
+            .parse::<Problem>()
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!((e.line, e.column), (3, 10));
+        assert_eq!(e.message, "duplicate rating");
     }
 
     #[test]
     fn test_solve_part_1() {
         let p: Problem = TEST_INPUT.parse().unwrap();
-        assert_eq!(solve_part_1(&p), 19114);
+        assert_eq!(solve_part_1(&p).unwrap(), 19114);
+    }
+
+    #[test]
+    fn test_explain() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        let (_, notes) = trace::collect(|| solve_part_1(&p));
+        assert_eq!(
+            notes,
+            [
+                "{x=787,m=2655,a=1222,s=2876}: in -> qqz -> qs -> lnx -> A",
+                "{x=1679,m=44,a=2067,s=496}: in -> px -> rfg -> gd -> R",
+                "{x=2036,m=264,a=79,s=2244}: in -> qqz -> hdj -> pv -> A",
+                "{x=2461,m=1339,a=466,s=291}: in -> px -> qkq -> crn -> R",
+                "{x=2127,m=1623,a=2188,s=1013}: in -> px -> rfg -> A",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_workflows() {
+        let ratings = "\n\n{x=787,m=2655,a=1222,s=2876}";
+
+        let cycle = format!("in{{x>10:px,R}}\npx{{s<5:in,A}}{ratings}");
+        let e = cycle.parse::<Problem>().unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "workflows can loop forever, cycle: \"in\" -> \"px\" -> \"in\""
+        );
+
+        let unknown = format!("in{{x>10:px,R}}{ratings}");
+        assert!(unknown.parse::<Problem>().is_err());
+
+        let no_start = format!("px{{x>10:A,R}}{ratings}");
+        assert!(no_start.parse::<Problem>().is_err());
+    }
+
+    #[test]
+    fn test_trailing_whitespace() {
+        // the line between workflows and part ratings only looks blank
+        let p: Problem = TEST_INPUT.replace('\n', " \r\n").parse().unwrap();
+        assert_eq!(p.workflows.len(), 11);
+        assert_eq!(p.part_ratings.len(), 5);
+        assert_eq!(solve_part_1(&p).unwrap(), 19114);
     }
 }
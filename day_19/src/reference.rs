@@ -0,0 +1,55 @@
+//! Slow, but obviously correct solvers, to check the ones in the crate root against.
+
+use anyhow::anyhow;
+
+use crate::{Problem, Relation, Rule};
+
+/// # Errors
+///
+/// Returns error when the sum of ratings of accepted parts doesn't fit in u64.
+pub fn solve_part_1(p: &Problem) -> Result<u64, anyhow::Error> {
+    let mut sum = 0u128;
+
+    for part in &p.part_ratings {
+        let mut name = "in";
+
+        while let Some(workflow) = p.workflows.get(name) {
+            let matching = workflow.rules.iter().find(|rule| {
+                let Rule {
+                    property,
+                    relation,
+                    value,
+                    ..
+                } = rule;
+                let rating = part.0[usize::from(property)];
+
+                match relation {
+                    Relation::GT => rating > *value,
+                    Relation::LT => rating < *value,
+                }
+            });
+
+            name = matching.map_or(&workflow.fallback_workflow_name, |r| {
+                &r.target_workflow_name
+            });
+        }
+
+        if name == "A" {
+            sum += part.0.iter().map(|&r| u128::from(r)).sum::<u128>();
+        }
+    }
+
+    u64::try_from(sum).map_err(|_| anyhow!("sum of ratings overflows u64"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_INPUT;
+
+    #[test]
+    fn test_solve_part_1() {
+        let p: Problem = TEST_INPUT.parse().unwrap();
+        assert_eq!(solve_part_1(&p).unwrap(), 19114);
+    }
+}